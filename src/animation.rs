@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use bevy::{asset::AssetPath, prelude::*, utils::HashMap};
+use serde::Deserialize;
 
 use crate::{misc::read_img, state::AppState};
 
@@ -156,7 +157,8 @@ impl Animation {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AnimationMode {
     /// L'animation est joué qu'une seule fois pour les attaques par exemple
     Once,
@@ -166,7 +168,8 @@ pub enum AnimationMode {
     Repeating,
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AnimationDirection {
     /// L'animation se joue dans l'ordre normale
     #[default]
@@ -223,7 +226,7 @@ pub enum AnimationError {
 macro_rules! animation_maker {
     ($assets_server:expr, $asset_type:ident, $tile_size:expr, [ $( $name:expr => ($duration:expr, $mode:expr, $direction:expr) ),* ]) => {{
         use std::time::Duration;
-        use crate::animation::{AnimationMode, Animation, AnimationDirection};
+        use $crate::animation::{AnimationMode, Animation, AnimationDirection};
 
         let mut map = HashMap::new();
         $(
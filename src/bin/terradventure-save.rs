@@ -0,0 +1,185 @@
+//! Inspects and edits world saves without starting the game
+//!
+//! ```text
+//! terradventure-save list
+//! terradventure-save dump <save> [output.json]
+//! terradventure-save import <save> <input.json>
+//! terradventure-save summary <save>
+//! terradventure-save export <save> [output.tdworld]
+//! terradventure-save import-archive <input.tdworld>
+//! ```
+
+use bevy::utils::HashMap;
+use std::{env::args, fs, path::Path, process::ExitCode};
+use terradventure::{
+    items::{item::ItemTrait, stack::ItemStack},
+    mob::MobTrait,
+    player::{class::PlayerClass, inventory::Slot},
+    save::{Save, SAVES_DIR},
+};
+
+fn main() -> ExitCode {
+    let args = args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>();
+
+    let res = match args.as_slice() {
+        ["list"] => list(),
+        ["dump", name] => dump(name, None),
+        ["dump", name, output] => dump(name, Some(output)),
+        ["import", name, input] => import(name, input),
+        ["summary", name] => summary(name),
+        ["export", name] => export(name, None),
+        ["export", name, output] => export(name, Some(output)),
+        ["import-archive", input] => import_archive(input),
+        _ => Err(format!(
+            "Usage : terradventure-save <list | dump <save> [output.json] | import <save> <input.json> | summary <save> | export <save> [output.{}] | import-archive <input.{}>>\nSaves directory : {}",
+            Save::ARCHIVE_EXTENSION,
+            Save::ARCHIVE_EXTENSION,
+            SAVES_DIR.display()
+        )),
+    };
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list() -> Result<(), String> {
+    for save in Save::get_saves() {
+        match save {
+            Ok((ident, meta)) => println!(
+                "{ident}\t{}\tlast played {}",
+                meta.name,
+                meta.last_played.format("%d/%m/%Y %H:%M")
+            ),
+            Err(err) => println!("<corrupted>\t{err}"),
+        }
+    }
+
+    Ok(())
+}
+
+fn dump(name: &str, output: Option<&str>) -> Result<(), String> {
+    let save = Save::read(name).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&save).map_err(|e| e.to_string())?;
+
+    match output {
+        Some(path) => fs::write(path, json).map_err(|e| e.to_string()),
+        None => {
+            println!("{json}");
+            Ok(())
+        }
+    }
+}
+
+fn import(name: &str, input: &str) -> Result<(), String> {
+    let json = fs::read(input).map_err(|e| e.to_string())?;
+    let save: Save = serde_json::from_slice(&json).map_err(|e| e.to_string())?;
+    save.save_world(name).map_err(|e| e.to_string())?;
+
+    println!("Imported {input} into {name}");
+    Ok(())
+}
+
+fn export(name: &str, output: Option<&str>) -> Result<(), String> {
+    let path = match output {
+        Some(path) => {
+            Save::export(name, Path::new(path)).map_err(|e| e.to_string())?;
+            Path::new(path).to_path_buf()
+        }
+        None => Save::export_default(name).map_err(|e| e.to_string())?,
+    };
+
+    println!("Exported {name} to {}", path.display());
+    Ok(())
+}
+
+fn import_archive(input: &str) -> Result<(), String> {
+    let name = Save::import(Path::new(input)).map_err(|e| e.to_string())?;
+
+    println!("Imported {input} as {name}");
+    Ok(())
+}
+
+fn summary(name: &str) -> Result<(), String> {
+    let save = Save::read(name).map_err(|e| e.to_string())?;
+    let player = &save.player;
+
+    println!("Class : {}", player.player.class.name());
+    println!(
+        "Level : {} ({} xp, {} unspent points)",
+        player.level.level, player.level.xp, player.level.stat_points
+    );
+    println!("Money : {}", player.player.money.get());
+    println!("Mana : {:.1}", player.player.mana.get());
+    println!(
+        "Health : {:.1}/{:.1}",
+        player.stats.health, player.stats.max_health
+    );
+    println!("Position : {}", player.pos);
+    println!(
+        "Current world : {}.{}",
+        save.current_world.get_type(),
+        save.current_world.name()
+    );
+    println!("Seed : {}", save.seed);
+    if !save.defeated_bosses.is_empty() {
+        println!("Defeated bosses : {}", save.defeated_bosses.join(", "));
+    }
+    if let Some(checkpoint) = &save.checkpoint {
+        println!(
+            "Checkpoint : {} in {}.{}",
+            checkpoint.spawn_point,
+            checkpoint.world.get_type(),
+            checkpoint.world.name()
+        );
+    }
+    println!("Deaths : {}", save.deaths.len());
+
+    let inventory = &player.player.inventory;
+    println!("Inventory :");
+    for (field, slots) in [
+        ("pockets", inventory.pockets.as_slice()),
+        ("ressources", inventory.ressources.as_slice()),
+        ("armor", inventory.armor.as_slice()),
+        ("accessories", inventory.accessories.as_slice()),
+    ] {
+        for (index, stack) in slots.iter().enumerate().filter_map(slot_stack) {
+            println!(
+                "  {field}[{index}] : {} x{}",
+                stack.item.name().get(),
+                stack.actual_count()
+            );
+        }
+    }
+
+    for (world, world_save) in save.worlds.iter() {
+        println!("World {}.{} :", world.get_type(), world.name());
+
+        let mut mob_counts = HashMap::<&str, usize>::new();
+        for mob in world_save.mobs.iter() {
+            *mob_counts.entry(mob.data.name()).or_default() += 1;
+        }
+
+        for (mob, count) in mob_counts {
+            println!("  {mob} x{count}");
+        }
+        println!("  {} dropped items", world_save.items.len());
+        if let Some(weather) = &world_save.weather {
+            println!("  Weather : {:?}", weather.current);
+        }
+        if let Some(seed) = world_save.dungeon_seed {
+            println!("  Dungeon seed : {seed}");
+        }
+    }
+
+    Ok(())
+}
+
+fn slot_stack((index, slot): (usize, &Slot)) -> Option<(usize, &ItemStack)> {
+    slot.item.as_ref().map(|stack| (index, stack))
+}
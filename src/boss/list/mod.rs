@@ -0,0 +1,34 @@
+use enum_dispatch::enum_dispatch;
+use std::str::FromStr;
+
+use super::BossTrait;
+
+use self::pharaoh::Pharaoh;
+
+pub mod pharaoh;
+
+#[derive(Clone)]
+#[enum_dispatch(BossTrait)]
+pub enum BossObject {
+    Pharaoh(Pharaoh),
+}
+
+impl BossObject {
+    /// # Returns
+    /// Every boss of the game
+    pub fn all() -> Vec<Self> {
+        vec![Pharaoh.into()]
+    }
+}
+
+impl FromStr for BossObject {
+    type Err = ();
+
+    /// Parses the [`BossTrait::name`] of a boss
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|boss| boss.name() == s)
+            .ok_or(())
+    }
+}
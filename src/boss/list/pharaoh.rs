@@ -0,0 +1,64 @@
+use crate::{
+    boss::{
+        pattern::{BossAttack, BossPhase},
+        BossTrait,
+    },
+    mob::{definition::DefinedMob, list::MobObject},
+};
+
+/// Guards the treasure at the bottom of the pyramid
+#[derive(Clone, Default)]
+pub struct Pharaoh;
+
+impl BossTrait for Pharaoh {
+    fn name(&self) -> &'static str {
+        "pharaoh"
+    }
+
+    fn mob(&self) -> MobObject {
+        DefinedMob::new("pharaoh").into()
+    }
+
+    fn phases(&self) -> Vec<BossPhase> {
+        let charge = BossAttack::Charge {
+            duration: 1.2,
+            speed_multiplier: 3.,
+        };
+
+        vec![
+            BossPhase {
+                health: 1.,
+                pattern: vec![
+                    BossAttack::Chase(4.),
+                    BossAttack::Wait(1.),
+                    charge.clone(),
+                    BossAttack::Wait(1.5),
+                ],
+            },
+            // Starts jumping at the player
+            BossPhase {
+                health: 0.5,
+                pattern: vec![
+                    BossAttack::Chase(3.),
+                    BossAttack::Leap,
+                    charge.clone(),
+                    BossAttack::Wait(0.8),
+                    BossAttack::Leap,
+                    BossAttack::Leap,
+                    BossAttack::Wait(1.),
+                ],
+            },
+            // No more chasing, only charges and leaps with short breaks
+            BossPhase {
+                health: 0.2,
+                pattern: vec![
+                    charge.clone(),
+                    BossAttack::Wait(0.5),
+                    BossAttack::Leap,
+                    charge,
+                    BossAttack::Wait(0.5),
+                ],
+            },
+        ]
+    }
+}
@@ -0,0 +1,282 @@
+pub mod list;
+pub mod pattern;
+
+use crate::{
+    damage::apply_damage,
+    items::loot_table::LootTable,
+    mob::{definition::MobDefinitions, list::MobObject, Mob, MobTrait},
+    player::{money::DropMoneyEvent, Player},
+    random::WorldRng,
+    save::{CurrentSave, SaveWorldEvent},
+    state::AppState,
+    stats::{self, Stats},
+    world::{is_loading, BLOCK_SIZE},
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use enum_dispatch::enum_dispatch;
+use std::path::{Path, PathBuf};
+
+use self::{
+    list::{pharaoh::Pharaoh, BossObject},
+    pattern::{BossPatternAi, BossPhase},
+};
+
+pub struct BossPlugin;
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_bosses,
+                lock_arenas,
+                update_phases,
+                // The boss is despawned by the stats update once its health runs out
+                defeat_bosses.after(apply_damage).before(stats::update),
+            )
+                .chain()
+                .run_if(in_state(AppState::InGame).and_then(not(is_loading))),
+        );
+    }
+}
+
+#[enum_dispatch]
+pub trait BossTrait: Sync + Send {
+    /// Used by Tiled maps, saves and lang files
+    fn name(&self) -> &'static str;
+
+    /// The mob fighting as the boss
+    fn mob(&self) -> MobObject;
+
+    /// Ordered by decreasing health, the first one starts the fight
+    fn phases(&self) -> Vec<BossPhase>;
+
+    /// Always dropped when the boss is defeated, on top of the loot of its mob
+    fn loot_table(&self) -> PathBuf {
+        Path::new("bosses").join(format!("{}.json", self.name()))
+    }
+}
+
+/// Room of a boss, spawned from the "Boss" objects of a Tiled map
+///
+/// The boss appears there until it's defeated and gates close the room while fighting it
+#[derive(Component)]
+pub struct BossArena {
+    pub boss: BossObject,
+    pub rect: Rect,
+    /// Walls closing the arena, empty if the arena is open
+    gates: Vec<Entity>,
+}
+
+impl BossArena {
+    pub fn new(boss: BossObject, rect: Rect) -> Self {
+        Self {
+            boss,
+            rect,
+            gates: Vec::new(),
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        !self.gates.is_empty()
+    }
+}
+
+#[derive(Component)]
+pub struct BossGate;
+
+impl BossGate {
+    const COLOR: Color = Color::rgb(0.35, 0.27, 0.16);
+}
+
+/// Mob fighting as a boss, it isn't saved with the other mobs since its [`BossArena`] spawns it again
+#[derive(Component)]
+pub struct Boss {
+    pub object: BossObject,
+    phases: Vec<BossPhase>,
+    /// Index of the phase whose pattern is played, [`None`] until the fight starts
+    pub phase: Option<usize>,
+    pub arena: Entity,
+    started: bool,
+    defeated: bool,
+}
+
+impl Boss {
+    pub fn new(object: BossObject, arena: Entity) -> Self {
+        Self {
+            phases: object.phases(),
+            object,
+            phase: None,
+            arena,
+            started: false,
+            defeated: false,
+        }
+    }
+
+    pub fn is_fighting(&self) -> bool {
+        self.started && !self.defeated
+    }
+}
+
+/// Whether the player is locked in a [`BossArena`]
+pub fn is_arena_locked(query: Query<&BossArena>) -> bool {
+    query.iter().any(|arena| arena.is_locked())
+}
+
+fn spawn_bosses(
+    mut commands: Commands,
+    query: Query<(Entity, &BossArena), Added<BossArena>>,
+    current_save: Res<CurrentSave>,
+    definitions: Res<MobDefinitions>,
+    asset_server: Res<AssetServer>,
+) {
+    for (entity, arena) in query.iter() {
+        let name = arena.boss.name();
+        let defeated = current_save.0.as_ref().is_some_and(|save_data| {
+            save_data
+                .read()
+                .defeated_bosses
+                .iter()
+                .any(|boss| boss == name)
+        });
+        if defeated {
+            continue;
+        }
+
+        let pos = Vec2::new(arena.rect.center().x, arena.rect.min.y + BLOCK_SIZE * 2.);
+        commands.spawn((
+            definitions
+                .resolve(arena.boss.mob())
+                .bundle(&asset_server, pos),
+            Boss::new(arena.boss.clone(), entity),
+        ));
+    }
+}
+
+/// Closes the arena once the player is well inside it and opens it when the boss is gone
+fn lock_arenas(
+    mut commands: Commands,
+    mut arena_query: Query<(Entity, &mut BossArena)>,
+    mut boss_query: Query<&mut Boss>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.xy();
+
+    for (entity, mut arena) in arena_query.iter_mut() {
+        let boss = boss_query
+            .iter_mut()
+            .find(|boss| boss.arena == entity && !boss.defeated);
+
+        match boss {
+            Some(mut boss) if !arena.is_locked() => {
+                // Far enough from the walls to not be stuck in a gate
+                if !arena.rect.inset(-2. * BLOCK_SIZE).contains(player_pos) {
+                    continue;
+                }
+
+                let center = arena.rect.center();
+                let size = Vec2::new(BLOCK_SIZE, arena.rect.height());
+                arena.gates = [arena.rect.min.x, arena.rect.max.x - BLOCK_SIZE]
+                    .into_iter()
+                    .map(|x| {
+                        let pos = Vec2::new(x + BLOCK_SIZE / 2., center.y);
+                        commands
+                            .spawn((
+                                BossGate,
+                                Collider::cuboid(size.x / 2., size.y / 2.),
+                                SpriteBundle {
+                                    sprite: Sprite {
+                                        color: BossGate::COLOR,
+                                        custom_size: Some(size),
+                                        ..Default::default()
+                                    },
+                                    transform: Transform::from_translation(
+                                        (pos - center).extend(1.),
+                                    ),
+                                    ..Default::default()
+                                },
+                            ))
+                            .id()
+                    })
+                    .collect();
+                commands.entity(entity).push_children(&arena.gates);
+
+                boss.started = true;
+                info!("Boss {} fight started", boss.object.name());
+            }
+            None if arena.is_locked() => {
+                for gate in arena.gates.drain(..) {
+                    commands.entity(gate).despawn_recursive();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Gives the boss the pattern of its phase, phases only go forward even if the boss heals
+fn update_phases(mut query: Query<(&mut Boss, &mut Mob, &Stats)>) {
+    for (mut boss, mut mob, stats) in query.iter_mut() {
+        if !boss.started {
+            continue;
+        }
+
+        let health = stats.health / stats.max_health;
+        let phase = boss
+            .phases
+            .iter()
+            .rposition(|phase| health <= phase.health)
+            .unwrap_or(0)
+            .max(boss.phase.unwrap_or(0));
+
+        if boss.phase == Some(phase) {
+            continue;
+        }
+
+        info!("Boss {} enters phase {}", boss.object.name(), phase + 1);
+        boss.phase = Some(phase);
+        mob.ai = Box::new(BossPatternAi::new(boss.phases[phase].pattern.clone()));
+    }
+}
+
+fn defeat_bosses(
+    mut commands: Commands,
+    mut query: Query<(&mut Boss, &Stats, &Transform)>,
+    mut current_save: ResMut<CurrentSave>,
+    mut money_event: EventWriter<DropMoneyEvent>,
+    mut save_event: EventWriter<SaveWorldEvent>,
+    asset_server: Res<AssetServer>,
+    mut rng: ResMut<WorldRng>,
+) {
+    for (mut boss, stats, transform) in query.iter_mut() {
+        if boss.defeated || stats.health > 0. {
+            continue;
+        }
+        boss.defeated = true;
+
+        let name = boss.object.name();
+        info!("Boss {name} defeated");
+
+        if let Some(save_data) = &mut current_save.0 {
+            let defeated_bosses = &mut save_data.data.defeated_bosses;
+            if !defeated_bosses.iter().any(|boss| boss == name) {
+                defeated_bosses.push(name.to_owned());
+            }
+        }
+
+        if let Some(loot_table) = LootTable::read(&boss.object.loot_table()) {
+            let pos = transform.translation.xy();
+            let (money, items) = loot_table.get_random(&mut *rng);
+
+            money_event.send(DropMoneyEvent { amount: money, pos });
+            for item in items {
+                commands.spawn(item.bundle(&asset_server, pos));
+            }
+        }
+
+        save_event.send(SaveWorldEvent);
+    }
+}
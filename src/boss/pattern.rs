@@ -0,0 +1,143 @@
+use crate::{
+    mob::ai::{MeleeAttack, MobAi, MobAiContext},
+    world::BLOCK_SIZE,
+};
+use bevy::prelude::*;
+
+/// Attacks of a boss until its health drops under the threshold of the next phase
+#[derive(Clone)]
+pub struct BossPhase {
+    /// Part of the max health, from 0 to 1, under which the phase starts
+    pub health: f32,
+    /// Played in a loop
+    pub pattern: Vec<BossAttack>,
+}
+
+/// One step of the attack pattern of a boss
+#[derive(Clone)]
+pub enum BossAttack {
+    /// Stands still facing the player for some seconds
+    Wait(f32),
+    /// Runs after the player and hits it for some seconds
+    Chase(f32),
+    /// Rushes towards where the player was, hurting it on contact
+    Charge {
+        duration: f32,
+        speed_multiplier: f32,
+    },
+    /// Jumps towards the player, hurting it on contact
+    Leap,
+}
+
+impl BossAttack {
+    fn duration(&self) -> f32 {
+        match *self {
+            Self::Wait(duration) | Self::Chase(duration) => duration,
+            Self::Charge { duration, .. } => duration,
+            Self::Leap => BossPatternAi::LEAP_DURATION,
+        }
+    }
+}
+
+/// Plays the pattern of a [`BossPhase`]
+pub struct BossPatternAi {
+    pattern: Vec<BossAttack>,
+    step: usize,
+    /// Time left for the current step, [`None`] if it hasn't started yet
+    timer: Option<Timer>,
+    attack: MeleeAttack,
+    /// Direction of the current charge or leap
+    direction: f32,
+    /// Whether the current charge or leap already hit the player
+    hit: bool,
+}
+
+impl BossPatternAi {
+    const LEAP_DURATION: f32 = 0.8;
+    const LEAP_SPEED_MULTIPLIER: f32 = 2.;
+    /// Charges and leaps hit harder than the melee attack
+    const CONTACT_DAMAGE_MULTIPLIER: f32 = 1.5;
+    /// How close the player must be to be hit by a charge or a leap
+    const CONTACT: Vec2 = Vec2::new(1.5 * BLOCK_SIZE, 2. * BLOCK_SIZE);
+
+    pub fn new(pattern: Vec<BossAttack>) -> Self {
+        Self {
+            pattern,
+            step: 0,
+            timer: None,
+            attack: MeleeAttack::default(),
+            direction: 1.,
+            hit: false,
+        }
+    }
+
+    /// # Returns
+    /// The base damage dealt to a player touching the boss, once per step
+    fn contact(&mut self, to_player: Option<Vec2>) -> Option<f32> {
+        let to_player = to_player?;
+        if self.hit || to_player.abs().cmpgt(Self::CONTACT).any() {
+            return None;
+        }
+
+        self.hit = true;
+        Some(MeleeAttack::DAMAGE * Self::CONTACT_DAMAGE_MULTIPLIER)
+    }
+}
+
+impl MobAi for BossPatternAi {
+    fn update(&mut self, context: &mut MobAiContext) -> Option<f32> {
+        let Some(attack) = self.pattern.get(self.step).cloned() else {
+            context.walk(0.);
+            return None;
+        };
+        let to_player = context.to_player();
+
+        if self.timer.is_none() {
+            self.direction = to_player.map_or(self.direction, |to_player| to_player.x.signum());
+            self.hit = false;
+            if matches!(attack, BossAttack::Leap) && context.grounded {
+                context.jump();
+            }
+        }
+
+        let timer = self
+            .timer
+            .get_or_insert_with(|| Timer::from_seconds(attack.duration(), TimerMode::Once));
+        timer.tick(context.time.delta());
+        if timer.finished() {
+            self.timer = None;
+            self.step = (self.step + 1) % self.pattern.len();
+        }
+
+        match attack {
+            BossAttack::Wait(_) => {
+                context.walk(0.);
+                if let Some(to_player) = to_player {
+                    context.sprite.flip_x = to_player.x < 0.;
+                }
+                None
+            }
+            BossAttack::Chase(_) => match to_player {
+                Some(to_player) => self.attack.update(context, to_player),
+                None => {
+                    context.walk(0.);
+                    None
+                }
+            },
+            BossAttack::Charge {
+                speed_multiplier, ..
+            } => {
+                if context.blocked(self.direction) {
+                    context.walk(0.);
+                } else {
+                    context.walk(self.direction * context.step(speed_multiplier));
+                }
+                self.contact(to_player)
+            }
+            BossAttack::Leap => {
+                context.walk(self.direction * context.step(Self::LEAP_SPEED_MULTIPLIER));
+                self.contact(to_player)
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     animation::AnimationController, interactable::Interactable, items::loot_table::LootTable,
-    player::money::DropMoneyEvent, state::AppState,
+    player::money::DropMoneyEvent, random::WorldRng, state::AppState,
 };
 use bevy::prelude::*;
 
@@ -30,6 +30,7 @@ fn chest_update(
     asset_server: Res<AssetServer>,
     time: Res<Time>,
     mut money_event: EventWriter<DropMoneyEvent>,
+    mut rng: ResMut<WorldRng>,
 ) {
     for (entity, chest, interactable, transform, mut animation_controller) in query.iter_mut() {
         if animation_controller.timer.percent() == 0. {
@@ -41,7 +42,7 @@ fn chest_update(
         }
 
         if animation_controller.just_finished.is_some() {
-            let (money, items) = chest.loot_table.get_random();
+            let (money, items) = chest.loot_table.get_random(&mut *rng);
 
             money_event.send(DropMoneyEvent {
                 amount: money,
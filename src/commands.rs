@@ -6,8 +6,10 @@ use std::{
 };
 
 use crate::{
+    day_night::{TimeOfDay, WorldClock},
     items::{list::Item, stack::ItemStack},
     player::{inventory::ui::UpdateSlotEvent, money::Money, Player},
+    weather::{Weather, WorldWeather},
 };
 
 #[derive(Event)]
@@ -45,6 +47,8 @@ pub fn handle_commands(
     mut command_event: EventReader<CommandEvent>,
     mut player_query: Query<(&mut Transform, &mut Player)>,
     mut update_slot_event: EventWriter<UpdateSlotEvent>,
+    mut clock: ResMut<WorldClock>,
+    mut weather_query: Query<&mut WorldWeather>,
 ) {
     let mut handle_command = |command: &CommandEvent| -> Result<(), String> {
         let command_data = command.msg.split(' ').collect::<Vec<_>>();
@@ -118,6 +122,55 @@ pub fn handle_commands(
                 Ok(())
             }
 
+            "time" => {
+                match args.first() {
+                    None => {}
+                    Some(&"set") => {
+                        let time = args.get(1).ok_or("Please specify a time")?;
+
+                        let time = match TimeOfDay::from_str(time) {
+                            Ok(time_of_day) => time_of_day.start(),
+                            Err(_) => {
+                                let hour: f32 = time
+                                    .parse()
+                                    .ok()
+                                    .filter(|hour| (0. ..24.).contains(hour))
+                                    .ok_or("Time must be dawn, day, dusk, night or an hour from 0 to 24")?;
+                                hour / 24.
+                            }
+                        };
+
+                        clock.set(time);
+                    }
+                    _ => return Err("Must be a valid action (set)".into()),
+                }
+
+                info!("{}", *clock);
+                Ok(())
+            }
+
+            "weather" => {
+                let mut weather = weather_query
+                    .get_single_mut()
+                    .map_err(|_| "World not found")?;
+
+                match args.first() {
+                    None => {}
+                    Some(&"set") => {
+                        let new_weather = args
+                            .get(1)
+                            .and_then(|name| Weather::from_str(name).ok())
+                            .ok_or("Weather must be clear, rain, sandstorm or fog")?;
+
+                        weather.set(new_weather);
+                    }
+                    _ => return Err("Must be a valid action (set)".into()),
+                }
+
+                info!("{}", *weather);
+                Ok(())
+            }
+
             _ => Err(format!("Command not found {command_name}")),
         }
     };
@@ -0,0 +1,206 @@
+use crate::{
+    effects::{Effect, EffectData, EffectsController, Modifier},
+    gui::styles::text_style,
+    mob::Mob,
+    player::Player,
+    random::WorldRng,
+    state::AppState,
+    stats::Stats,
+};
+use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::PI;
+
+pub struct DamagePlugin;
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>().add_systems(
+            Update,
+            (apply_damage, update_damage_taken).run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DamageType {
+    #[default]
+    Physical,
+    Fire,
+    /// Goes through the defence of the target
+    Poison,
+    /// Only half the defence of the target applies
+    Magic,
+}
+
+impl DamageType {
+    fn def_multiplier(&self) -> f32 {
+        match self {
+            Self::Physical | Self::Fire => 1.,
+            Self::Poison => 0.,
+            Self::Magic => 0.5,
+        }
+    }
+}
+
+/// Every hit goes through this event, it's scaled by the attacker then mitigated by the target
+#[derive(Event, Clone)]
+pub struct DamageEvent {
+    /// Whose strength and equipment scale the hit, [`None`] if nobody is behind it
+    pub source: Option<Entity>,
+    pub target: Entity,
+    /// Before scaling and mitigation
+    pub amount: f32,
+    pub typ: DamageType,
+    /// Given to the target along with the hit, unless it's immune to the type of the hit
+    pub effect: Option<(Effect, EffectData)>,
+}
+
+impl DamageEvent {
+    /// Extra damage for each point of strength, 0.1 is 10% more
+    const STRENGTH_SCALING: f32 = 0.1;
+    /// Part of the hit always dealt, whatever the defence of the target
+    const MIN_DAMAGE_RATIO: f32 = 0.1;
+    const CRIT_CHANCE: f32 = 0.1;
+    const CRIT_MULTIPLIER: f32 = 1.5;
+
+    pub fn new(source: Option<Entity>, target: Entity, amount: f32) -> Self {
+        Self {
+            source,
+            target,
+            amount,
+            typ: DamageType::default(),
+            effect: None,
+        }
+    }
+
+    pub fn with_type(mut self, typ: DamageType) -> Self {
+        self.typ = typ;
+        self
+    }
+
+    pub fn with_effect(mut self, effect: Effect, seconds: f32, level: u8) -> Self {
+        self.effect = Some((effect, EffectData::new(seconds, level)));
+        self
+    }
+}
+
+/// Floating number showing a hit
+#[derive(Component)]
+pub struct DamageTaken {
+    lifetime: Timer,
+}
+
+/// # Returns
+/// The strength and defence given on top of the [`Stats`] by the equipment of the player and the effects
+fn bonuses(player: Option<&Player>, effects: Option<&EffectsController>) -> (f32, f32) {
+    let (mut strength, mut def) = player.map_or((0., 0.), |player| {
+        (
+            player.inventory.equipment_strength(),
+            player.inventory.equipment_def(),
+        )
+    });
+
+    if let Some(effects) = effects {
+        strength += effects.total(Modifier::Strength);
+        def += effects.total(Modifier::Def);
+    }
+
+    (strength, def)
+}
+
+pub fn apply_damage(
+    mut commands: Commands,
+    mut damage_event: EventReader<DamageEvent>,
+    mut query: Query<(
+        &mut Stats,
+        &Transform,
+        Option<&Player>,
+        Option<&mut Mob>,
+        Option<&mut EffectsController>,
+    )>,
+    asset_server: Res<AssetServer>,
+    mut rng: ResMut<WorldRng>,
+) {
+    for event in damage_event.read() {
+        let strength = event
+            .source
+            .and_then(|source| query.get(source).ok())
+            .map_or(0., |(stats, _, player, _, effects)| {
+                stats.strength + bonuses(player, effects).0
+            });
+
+        let Ok((mut stats, transform, player, mob, effects)) = query.get_mut(event.target) else {
+            continue;
+        };
+
+        // Already dying
+        if stats.health <= 0. || event.amount <= 0. {
+            continue;
+        }
+
+        let def_bonus = bonuses(player, effects.as_deref()).1;
+        let resistance = stats.resistances.get(event.typ);
+        if resistance < 1. {
+            if let (Some((effect, data)), Some(mut effects)) = (event.effect.clone(), effects) {
+                effects.add(effect, data);
+            }
+        }
+
+        // Only an attacker lands critical hits
+        let critical = event.source.is_some() && rng.gen::<f32>() < DamageEvent::CRIT_CHANCE;
+        let mut amount =
+            event.amount * (1. + strength * DamageEvent::STRENGTH_SCALING) * (1. - resistance);
+        if critical {
+            amount *= DamageEvent::CRIT_MULTIPLIER;
+        }
+
+        let def = (stats.def + def_bonus) * event.typ.def_multiplier();
+        let amount = (amount - def).max(amount * DamageEvent::MIN_DAMAGE_RATIO);
+        // Immune
+        if amount <= 0. {
+            continue;
+        }
+        stats.health -= amount;
+
+        if let Some(mut mob) = mob {
+            mob.hit_animation();
+        }
+
+        let mut text_transform = Transform::from_translation(transform.translation);
+        text_transform.translation.z += 1.0;
+        text_transform.rotate_z(rng.gen::<f32>() * PI / 4. - PI / 8.);
+
+        commands.spawn((
+            DamageTaken {
+                lifetime: Timer::from_seconds(1.0, TimerMode::Once),
+            },
+            Text2dBundle {
+                text: Text::from_section(
+                    format!("-{amount:.1}"),
+                    TextStyle {
+                        color: if critical { Color::YELLOW } else { Color::RED },
+                        font_size: if critical { 20. } else { 15. },
+                        ..text_style(&asset_server)
+                    },
+                ),
+                transform: text_transform,
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+fn update_damage_taken(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut DamageTaken, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut damage_taken, mut transform) in query.iter_mut() {
+        damage_taken.lifetime.tick(time.delta());
+        transform.translation.y += 50.0 * time.delta_seconds();
+
+        if damage_taken.lifetime.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    background::ParallaxBackgroundLayerItem, save::SaveData, state::AppState, world::is_loading,
+};
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TileColor;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+pub struct DayNightPlugin;
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldClock>()
+            .add_systems(Update, load_clock)
+            .add_systems(
+                Update,
+                (tick_clock.run_if(not(is_loading)), apply_daylight)
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+/// Time of the world, saved with the rest of the [`Save`](crate::save::Save)
+#[derive(Resource, Serialize, Deserialize, Clone)]
+pub struct WorldClock {
+    pub day: u32,
+    /// Progress of the current day, 0 being midnight and 0.5 noon
+    pub time: f32,
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self {
+            day: 0,
+            time: TimeOfDay::Day.start(),
+        }
+    }
+}
+
+impl WorldClock {
+    /// Real seconds for a whole in game day
+    pub const DAY_DURATION: f32 = 1200.;
+
+    /// Brightness of the world when it's the darkest
+    const NIGHT_BRIGHTNESS: f32 = 0.35;
+
+    pub fn advance(&mut self, seconds: f32) {
+        self.time += seconds / Self::DAY_DURATION;
+        while self.time >= 1. {
+            self.time -= 1.;
+            self.day += 1;
+        }
+    }
+
+    /// Moves forward to the next given time, a day passes if it's already later
+    pub fn set(&mut self, time: f32) {
+        let time = time.rem_euclid(1.);
+        if time < self.time {
+            self.day += 1;
+        }
+        self.time = time;
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        match self.time {
+            t if t < TimeOfDay::Dawn.start() => TimeOfDay::Night,
+            t if t < TimeOfDay::Day.start() => TimeOfDay::Dawn,
+            t if t < TimeOfDay::Dusk.start() => TimeOfDay::Day,
+            t if t < TimeOfDay::Night.start() => TimeOfDay::Dusk,
+            _ => TimeOfDay::Night,
+        }
+    }
+
+    /// # Returns
+    /// How lit the world is, from [`Self::NIGHT_BRIGHTNESS`] to 1
+    pub fn daylight(&self) -> f32 {
+        let transition = |from: f32, to: f32| ((self.time - from) / (to - from)).clamp(0., 1.);
+
+        let light = match self.time_of_day() {
+            TimeOfDay::Night => 0.,
+            TimeOfDay::Dawn => transition(TimeOfDay::Dawn.start(), TimeOfDay::Day.start()),
+            TimeOfDay::Day => 1.,
+            TimeOfDay::Dusk => 1. - transition(TimeOfDay::Dusk.start(), TimeOfDay::Night.start()),
+        };
+
+        Self::NIGHT_BRIGHTNESS + (1. - Self::NIGHT_BRIGHTNESS) * light
+    }
+
+    /// Color multiplied with the tilemaps and the backgrounds, night is a bit blue
+    pub fn tint(&self) -> Color {
+        let light = self.daylight();
+        Color::rgb(light, light, light.powf(0.5))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeOfDay {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+impl TimeOfDay {
+    /// # Returns
+    /// The [`WorldClock::time`] at which this part of the day starts
+    pub fn start(&self) -> f32 {
+        match self {
+            Self::Dawn => 0.2,
+            Self::Day => 0.3,
+            Self::Dusk => 0.7,
+            Self::Night => 0.8,
+        }
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dawn" => Ok(Self::Dawn),
+            "day" => Ok(Self::Day),
+            "dusk" => Ok(Self::Dusk),
+            "night" => Ok(Self::Night),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for WorldClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let minutes = (self.time * 24. * 60.) as u32;
+        write!(
+            f,
+            "Day {} {:02}:{:02} ({:?})",
+            self.day,
+            minutes / 60,
+            minutes % 60,
+            self.time_of_day()
+        )
+    }
+}
+
+fn load_clock(mut event: EventReader<SaveData>, mut clock: ResMut<WorldClock>) {
+    for ev in event.read() {
+        *clock = ev.read().clock.clone();
+    }
+}
+
+fn tick_clock(mut clock: ResMut<WorldClock>, time: Res<Time>) {
+    clock.advance(time.delta_seconds());
+}
+
+fn apply_daylight(
+    clock: Res<WorldClock>,
+    mut tiles: Query<&mut TileColor>,
+    mut backgrounds: Query<&mut Sprite, With<ParallaxBackgroundLayerItem>>,
+) {
+    let tint = clock.tint();
+
+    for mut color in tiles.iter_mut() {
+        if color.0 != tint {
+            color.0 = tint;
+        }
+    }
+
+    for mut sprite in backgrounds.iter_mut() {
+        sprite.color = tint;
+    }
+}
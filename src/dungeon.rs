@@ -0,0 +1,598 @@
+use crate::{
+    lang::Lang,
+    mob::{definition::MobDefinitions, spawn::GroundSpots},
+    random::WorldRng,
+    save::CurrentSave,
+    state::AppState,
+    tiled::{spawn_map, Loaded, MapContext, MapPlacement, TiledLayersStorage, TiledMap},
+    world::World,
+};
+use bevy::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::{cmp::Reverse, str::FromStr};
+use tiled::{ObjectShape, PropertyValue};
+
+pub struct DungeonPlugin;
+impl Plugin for DungeonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, generate_dungeons.run_if(in_state(AppState::InGame)));
+    }
+}
+
+/// Room maps a dungeon is generated from, they are in `tiled/dungeon/<dungeon name>/`
+pub struct DungeonTemplates {
+    pub entrance: &'static str,
+    pub rooms: &'static [&'static str],
+    pub boss: Option<&'static str>,
+    /// Rooms placed between the entrance and the boss room
+    pub room_count: usize,
+}
+
+impl DungeonTemplates {
+    pub fn load(&self, world: &World, asset_server: &AssetServer) -> DungeonRooms {
+        DungeonRooms {
+            entrance: asset_server.load(world.room_path(self.entrance)),
+            rooms: self
+                .rooms
+                .iter()
+                .map(|room| asset_server.load(world.room_path(room)))
+                .collect(),
+            boss: self
+                .boss
+                .map(|boss| asset_server.load(world.room_path(boss))),
+            room_count: self.room_count,
+        }
+    }
+}
+
+/// Loaded [`DungeonTemplates`], the dungeon is generated once every room is loaded
+#[derive(Component)]
+pub struct DungeonRooms {
+    pub entrance: Handle<TiledMap>,
+    pub rooms: Vec<Handle<TiledMap>>,
+    pub boss: Option<Handle<TiledMap>>,
+    pub room_count: usize,
+}
+
+impl DungeonRooms {
+    pub fn handles(&self) -> impl Iterator<Item = &Handle<TiledMap>> {
+        std::iter::once(&self.entrance)
+            .chain(self.rooms.iter())
+            .chain(self.boss.iter())
+    }
+}
+
+/// Seed of the layout of a generated dungeon, saved so loading the save rebuilds the same layout
+#[derive(Component, Clone, Copy)]
+pub struct DungeonSeed(pub u64);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConnectorDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl ConnectorDirection {
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+        }
+    }
+
+    /// Towards the room on the other side of the connector
+    pub fn step(&self) -> IVec2 {
+        match self {
+            Self::Left => IVec2::NEG_X,
+            Self::Right => IVec2::X,
+            Self::Up => IVec2::Y,
+            Self::Down => IVec2::NEG_Y,
+        }
+    }
+}
+
+impl FromStr for ConnectorDirection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "up" => Ok(Self::Up),
+            "down" => Ok(Self::Down),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Door of a room template, spawned from the "Connector" rectangle objects of a Tiled map
+///
+/// The tiles under a connector are removed when another room is attached to it
+#[derive(Clone)]
+pub struct Connector {
+    pub direction: ConnectorDirection,
+    /// In tiles from the bottom left of the room
+    pub rect: IRect,
+}
+
+pub struct RoomTemplate {
+    /// In tiles
+    pub size: IVec2,
+    pub connectors: Vec<Connector>,
+}
+
+impl RoomTemplate {
+    pub fn from_map(map: &tiled::Map) -> Self {
+        let tile_width = map.tile_width as f32;
+        let tile_height = map.tile_height as f32;
+        let map_height = map.height as i32;
+
+        let mut connectors = Vec::new();
+        for layer in map.layers() {
+            let tiled::LayerType::Objects(object_layer) = layer.layer_type() else {
+                continue;
+            };
+
+            for object in object_layer.objects() {
+                if object.user_type != "Connector" {
+                    continue;
+                }
+
+                let ObjectShape::Rect { width, height } = object.shape else {
+                    warn!("Connector {} must be a rectangle", object.name);
+                    continue;
+                };
+
+                let direction = match object.properties.get("direction") {
+                    Some(PropertyValue::StringValue(s)) => ConnectorDirection::from_str(s).ok(),
+                    _ => None,
+                };
+
+                let Some(direction) = direction else {
+                    warn!("Connector {} has no valid direction", object.name);
+                    continue;
+                };
+
+                // Tiled rows go downwards
+                let top = (object.y / tile_height).floor() as i32;
+                let bottom = ((object.y + height) / tile_height).ceil() as i32;
+
+                connectors.push(Connector {
+                    direction,
+                    rect: IRect::new(
+                        (object.x / tile_width).floor() as i32,
+                        map_height - bottom,
+                        ((object.x + width) / tile_width).ceil() as i32,
+                        map_height - top,
+                    ),
+                });
+            }
+        }
+
+        Self {
+            size: IVec2::new(map.width as i32, map_height),
+            connectors,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RoomKind {
+    Entrance,
+    /// Index in [`DungeonTemplates::rooms`]
+    Room(usize),
+    Boss,
+}
+
+pub struct PlacedRoom {
+    pub kind: RoomKind,
+    /// Position of the bottom left tile of the room, in tiles
+    pub offset: IVec2,
+    pub size: IVec2,
+    /// Rooms between this one and the entrance
+    pub depth: usize,
+    /// Connectors opened towards another room
+    pub carved: Vec<IRect>,
+}
+
+/// Rooms of a dungeon and how they are connected, always the same for a given seed
+#[derive(Default)]
+pub struct DungeonLayout {
+    pub rooms: Vec<PlacedRoom>,
+    /// Connected rooms, by index in [`Self::rooms`]
+    pub links: Vec<(usize, usize)>,
+}
+
+impl DungeonLayout {
+    const ATTEMPTS_PER_ROOM: usize = 20;
+
+    /// Grows a tree of rooms from the entrance, the boss room is attached to the deepest room possible
+    pub fn generate(
+        seed: u64,
+        entrance: &RoomTemplate,
+        rooms: &[RoomTemplate],
+        boss: Option<&RoomTemplate>,
+        room_count: usize,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut builder = LayoutBuilder::default();
+        builder.place(RoomKind::Entrance, entrance, IVec2::ZERO, 0);
+
+        let mut attempts = 0;
+        while builder.layout.rooms.len() <= room_count
+            && attempts < room_count * Self::ATTEMPTS_PER_ROOM
+            && !rooms.is_empty()
+        {
+            attempts += 1;
+
+            let Some(&open) = builder.open.choose(&mut rng) else {
+                break;
+            };
+
+            let index = rng.gen_range(0..rooms.len());
+            builder.attach(open, RoomKind::Room(index), &rooms[index], &mut rng);
+        }
+
+        if let Some(boss) = boss {
+            let mut candidates = builder.open.clone();
+            candidates.sort_by_key(|(room, _)| Reverse(builder.layout.rooms[*room].depth));
+
+            let placed = candidates
+                .into_iter()
+                .any(|open| builder.attach(open, RoomKind::Boss, boss, &mut rng));
+
+            if !placed {
+                warn!("No room left for the boss of the dungeon (seed {seed})");
+            }
+        }
+
+        builder.layout
+    }
+
+    /// # Returns
+    /// The middle of the dungeon in tiles, to center it on the origin like the other maps
+    fn center(&self) -> Vec2 {
+        let min = self
+            .rooms
+            .iter()
+            .map(|room| room.offset)
+            .fold(IVec2::MAX, IVec2::min);
+        let max = self
+            .rooms
+            .iter()
+            .map(|room| room.offset + room.size)
+            .fold(IVec2::MIN, IVec2::max);
+
+        (min + max).as_vec2() / 2.
+    }
+
+    pub fn placement(&self, index: usize, tile_size: Vec2) -> MapPlacement {
+        let room = &self.rooms[index];
+
+        MapPlacement {
+            transform: Transform::from_translation(
+                ((room.offset.as_vec2() - self.center()) * tile_size).extend(0.),
+            ),
+            room: Some(index as u32),
+            carved: room.carved.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct LayoutBuilder<'a> {
+    layout: DungeonLayout,
+    /// Template of each placed room
+    templates: Vec<&'a RoomTemplate>,
+    /// Connectors without a room behind them yet, (room, connector)
+    open: Vec<(usize, usize)>,
+}
+
+impl<'a> LayoutBuilder<'a> {
+    fn place(
+        &mut self,
+        kind: RoomKind,
+        template: &'a RoomTemplate,
+        offset: IVec2,
+        depth: usize,
+    ) -> usize {
+        let index = self.layout.rooms.len();
+
+        self.layout.rooms.push(PlacedRoom {
+            kind,
+            offset,
+            size: template.size,
+            depth,
+            carved: Vec::new(),
+        });
+        self.templates.push(template);
+        self.open
+            .extend((0..template.connectors.len()).map(|connector| (index, connector)));
+
+        index
+    }
+
+    /// Tries to put a room behind an open connector
+    /// # Returns
+    /// Whether the room fit
+    fn attach(
+        &mut self,
+        (from, from_connector): (usize, usize),
+        kind: RoomKind,
+        template: &'a RoomTemplate,
+        rng: &mut StdRng,
+    ) -> bool {
+        let from_template = self.templates[from];
+        let exit = &from_template.connectors[from_connector];
+        let from_offset = self.layout.rooms[from].offset;
+        let depth = self.layout.rooms[from].depth + 1;
+
+        let mut candidates = template
+            .connectors
+            .iter()
+            .enumerate()
+            .filter(|(_, connector)| {
+                connector.direction == exit.direction.opposite()
+                    && connector.rect.size() == exit.rect.size()
+            })
+            .collect::<Vec<_>>();
+        candidates.shuffle(rng);
+
+        for (connector_index, connector) in candidates {
+            // Both connectors end up side by side
+            let offset = from_offset + exit.rect.min + exit.direction.step() * exit.rect.size()
+                - connector.rect.min;
+
+            if self.overlaps(offset, template.size) {
+                continue;
+            }
+
+            let index = self.place(kind, template, offset, depth);
+            self.open.retain(|open| {
+                *open != (from, from_connector) && *open != (index, connector_index)
+            });
+            self.layout.rooms[from].carved.push(exit.rect);
+            self.layout.rooms[index].carved.push(connector.rect);
+            self.layout.links.push((from, index));
+
+            return true;
+        }
+
+        false
+    }
+
+    fn overlaps(&self, offset: IVec2, size: IVec2) -> bool {
+        self.layout.rooms.iter().any(|room| {
+            offset.x < room.offset.x + room.size.x
+                && room.offset.x < offset.x + size.x
+                && offset.y < room.offset.y + room.size.y
+                && room.offset.y < offset.y + size.y
+        })
+    }
+}
+
+fn generate_dungeons(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &World,
+            &DungeonRooms,
+            &DungeonSeed,
+            &mut TiledLayersStorage,
+        ),
+        Without<Loaded>,
+    >,
+    maps: Res<Assets<TiledMap>>,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+    current_save: Res<CurrentSave>,
+    mob_definitions: Res<MobDefinitions>,
+    mut rng: ResMut<WorldRng>,
+) {
+    for (entity, world, rooms, seed, mut layer_storage) in query.iter_mut() {
+        let Some(entrance) = maps.get(&rooms.entrance) else {
+            continue;
+        };
+
+        let Some(room_maps) = rooms
+            .rooms
+            .iter()
+            .map(|room| maps.get(room))
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+
+        let boss = match &rooms.boss {
+            Some(boss) => match maps.get(boss) {
+                Some(boss) => Some(boss),
+                None => continue,
+            },
+            None => None,
+        };
+
+        let templates = room_maps
+            .iter()
+            .map(|room| RoomTemplate::from_map(&room.map))
+            .collect::<Vec<_>>();
+
+        let layout = DungeonLayout::generate(
+            seed.0,
+            &RoomTemplate::from_map(&entrance.map),
+            &templates,
+            boss.map(|boss| RoomTemplate::from_map(&boss.map)).as_ref(),
+            rooms.room_count,
+        );
+
+        info!(
+            "Generated dungeon {} with {} rooms from seed {}",
+            world.name(),
+            layout.rooms.len(),
+            seed.0
+        );
+
+        let context = MapContext {
+            entity,
+            world,
+            world_data: current_save
+                .0
+                .as_ref()
+                .and_then(|save| save.data.worlds.get(world)),
+            asset_server: &asset_server,
+            lang: &lang,
+            mob_definitions: &mob_definitions,
+        };
+
+        let tile_size = Vec2::new(
+            entrance.map.tile_width as f32,
+            entrance.map.tile_height as f32,
+        );
+
+        let mut ground_spots = Vec::new();
+        for (index, room) in layout.rooms.iter().enumerate() {
+            let tiled_map = match room.kind {
+                RoomKind::Entrance => Some(entrance),
+                RoomKind::Room(template) => room_maps.get(template).copied(),
+                RoomKind::Boss => boss,
+            };
+
+            let Some(tiled_map) = tiled_map else {
+                continue;
+            };
+
+            spawn_map(
+                &mut commands,
+                &context,
+                tiled_map,
+                &layout.placement(index, tile_size),
+                &mut layer_storage,
+                &mut ground_spots,
+                &mut rng,
+            );
+        }
+
+        commands
+            .entity(entity)
+            .insert((GroundSpots(ground_spots), Loaded));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::{IRect, IVec2};
+
+    use super::{Connector, ConnectorDirection, DungeonLayout, RoomKind, RoomTemplate};
+
+    /// Room with a two tiles high door on its left and right sides and a two tiles wide one on top and bottom
+    fn room(width: i32, height: i32) -> RoomTemplate {
+        let connector = |direction, rect| Connector { direction, rect };
+
+        RoomTemplate {
+            size: IVec2::new(width, height),
+            connectors: vec![
+                connector(ConnectorDirection::Left, IRect::new(0, 1, 1, 3)),
+                connector(
+                    ConnectorDirection::Right,
+                    IRect::new(width - 1, 1, width, 3),
+                ),
+                connector(ConnectorDirection::Down, IRect::new(2, 0, 4, 1)),
+                connector(ConnectorDirection::Up, IRect::new(2, height - 1, 4, height)),
+            ],
+        }
+    }
+
+    fn generate(seed: u64) -> DungeonLayout {
+        DungeonLayout::generate(
+            seed,
+            &room(8, 6),
+            &[room(10, 6), room(6, 8), room(12, 10)],
+            Some(&room(16, 10)),
+            8,
+        )
+    }
+
+    /// What tells two layouts apart
+    fn summary(layout: &DungeonLayout) -> (Vec<(RoomKind, IVec2, IVec2)>, Vec<(usize, usize)>) {
+        let rooms = layout
+            .rooms
+            .iter()
+            .map(|room| (room.kind, room.offset, room.size))
+            .collect();
+        (rooms, layout.links.clone())
+    }
+
+    #[test]
+    fn same_seed_same_layout() {
+        assert_eq!(summary(&generate(42)), summary(&generate(42)));
+    }
+
+    #[test]
+    fn seeds_change_the_layout() {
+        let first = summary(&generate(0));
+        assert!((1..10).any(|seed| summary(&generate(seed)) != first));
+    }
+
+    #[test]
+    fn rooms_are_stitched_without_overlapping() {
+        for seed in 0..20 {
+            let layout = generate(seed);
+            let rooms = &layout.rooms;
+
+            assert!(rooms.len() > 2, "seed {seed}");
+            assert_eq!(
+                rooms
+                    .iter()
+                    .filter(|room| room.kind == RoomKind::Boss)
+                    .count(),
+                1,
+                "seed {seed}"
+            );
+
+            for (index, room) in rooms.iter().enumerate() {
+                let rect = IRect::from_corners(room.offset, room.offset + room.size);
+                for other in &rooms[index + 1..] {
+                    let other_rect = IRect::from_corners(other.offset, other.offset + other.size);
+                    assert!(rect.intersect(other_rect).is_empty(), "seed {seed}");
+                }
+
+                let links = layout
+                    .links
+                    .iter()
+                    .filter(|(from, to)| *from == index || *to == index)
+                    .count();
+                assert_eq!(room.carved.len(), links, "seed {seed}");
+            }
+
+            // Every room but the entrance hangs from another one
+            assert_eq!(layout.links.len(), rooms.len() - 1, "seed {seed}");
+
+            for &(from, to) in &layout.links {
+                let carved = |index: usize| {
+                    let room = &rooms[index];
+                    room.carved
+                        .iter()
+                        .map(|rect| {
+                            IRect::from_corners(room.offset + rect.min, room.offset + rect.max)
+                        })
+                        .collect::<Vec<_>>()
+                };
+
+                // Both connectors of a link end up side by side
+                let stitched = carved(from).iter().any(|exit| {
+                    carved(to).iter().any(|entry| {
+                        exit.size() == entry.size()
+                            && [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+                                .iter()
+                                .any(|step| exit.min + *step * exit.size() == entry.min)
+                    })
+                });
+                assert!(stitched, "seed {seed}, link {from} -> {to}");
+            }
+        }
+    }
+}
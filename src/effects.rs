@@ -1,13 +1,79 @@
-use crate::state::AppState;
+use crate::{
+    damage::{DamageEvent, DamageType},
+    state::AppState,
+};
 use bevy::{prelude::*, utils::hashbrown::HashMap};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Component, PartialEq, Eq, Hash, Clone)]
+/// Levels start at 0, the modifier of an effect applies once more for each level
+#[derive(Serialize, Deserialize, Component, PartialEq, Eq, Hash, Clone, Debug)]
 pub enum Effect {
     Levitation,
+    Burning,
+    Poisoned,
+    Slowed,
+    Swiftness,
+    Strength,
+    Regeneration,
+    Resistance,
+    JumpBoost,
 }
 
-#[derive(Serialize, Deserialize, Component, Clone)]
+/// What an effect changes, the modifiers of every effect on an entity add up
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    /// Part of the speed gained, negative slows down
+    Speed,
+    Strength,
+    /// Health per second
+    Regen,
+    Def,
+    /// Damage of this type taken every second
+    DamagePerSecond(DamageType),
+    /// Part of the jump impulsion gained
+    Jump,
+    /// Goes up instead of falling, 1 is as fast as a jump
+    Levitation,
+}
+
+impl Effect {
+    /// # Returns
+    /// What the effect changes, and by how much at level 0
+    pub fn modifier(&self) -> (Modifier, f32) {
+        match self {
+            Self::Levitation => (Modifier::Levitation, 1.),
+            Self::Burning => (Modifier::DamagePerSecond(DamageType::Fire), 2.),
+            Self::Poisoned => (Modifier::DamagePerSecond(DamageType::Poison), 1.),
+            Self::Slowed => (Modifier::Speed, -0.2),
+            Self::Swiftness => (Modifier::Speed, 0.2),
+            Self::Strength => (Modifier::Strength, 2.),
+            Self::Regeneration => (Modifier::Regen, 1.),
+            Self::Resistance => (Modifier::Def, 1.),
+            Self::JumpBoost => (Modifier::Jump, 0.3),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Levitation => "levitation",
+            Self::Burning => "burning",
+            Self::Poisoned => "poisoned",
+            Self::Slowed => "slowed",
+            Self::Swiftness => "swiftness",
+            Self::Strength => "strength",
+            Self::Regeneration => "regeneration",
+            Self::Resistance => "resistance",
+            Self::JumpBoost => "jump_boost",
+        }
+    }
+
+    /// Shown in the HUD while the player has the effect
+    pub fn icon(&self) -> String {
+        format!("gui/hud/effects/{}.png", self.name())
+    }
+}
+
+#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Debug)]
 pub struct EffectData {
     pub timer: Timer,
     pub level: u8,
@@ -20,9 +86,16 @@ impl EffectData {
             level,
         }
     }
+
+    /// # Returns
+    /// Whether a whole second went by during the last tick
+    fn just_ticked_second(&self, delta: f32) -> bool {
+        let elapsed = self.timer.elapsed_secs();
+        elapsed.floor() > (elapsed - delta).floor()
+    }
 }
 
-#[derive(Serialize, Deserialize, Default, Component, Clone)]
+#[derive(Serialize, Deserialize, Default, Component, Clone, PartialEq, Debug)]
 pub struct EffectsController {
     effects: HashMap<Effect, EffectData>,
 }
@@ -36,6 +109,37 @@ impl EffectsController {
         self.effects.get(effect)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&Effect, &EffectData)> {
+        self.effects.iter()
+    }
+
+    /// # Returns
+    /// The sum of a modifier over all the effects, each one counted once per level
+    pub fn total(&self, modifier: Modifier) -> f32 {
+        self.effects
+            .iter()
+            .filter_map(|(effect, data)| {
+                let (effect_modifier, value) = effect.modifier();
+                (effect_modifier == modifier).then_some(value * (data.level as f32 + 1.))
+            })
+            .sum()
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        (1. + self.total(Modifier::Speed)).max(0.)
+    }
+
+    pub fn jump_multiplier(&self) -> f32 {
+        (1. + self.total(Modifier::Jump)).max(0.)
+    }
+
+    /// # Returns
+    /// How fast [`Modifier::Levitation`] lifts, [`None`] if nothing does
+    pub fn levitation(&self) -> Option<f32> {
+        let lift = self.total(Modifier::Levitation);
+        (lift > 0.).then_some(lift)
+    }
+
     pub fn add(&mut self, effect: Effect, data: EffectData) {
         if let Some(current_data) = self.effects.get_mut(&effect) {
             if current_data.timer.remaining_secs() < data.timer.remaining_secs() {
@@ -68,10 +172,14 @@ impl Plugin for EffectsPlugin {
     }
 }
 
-fn effects_update(mut query: Query<&mut EffectsController>, time: Res<Time>) {
+fn effects_update(
+    mut query: Query<(Entity, &mut EffectsController)>,
+    mut damage_event: EventWriter<DamageEvent>,
+    time: Res<Time>,
+) {
     let mut effects_to_remove = Vec::new();
 
-    for mut controller in query.iter_mut() {
+    for (entity, mut controller) in query.iter_mut() {
         for (effect, data) in controller.effects.iter_mut() {
             data.timer.tick(time.delta());
 
@@ -80,9 +188,14 @@ fn effects_update(mut query: Query<&mut EffectsController>, time: Res<Time>) {
                 continue;
             }
 
-            // Each effect's implementation
-            match effect {
-                Effect::Levitation => {} //Implemented in [`character_controller_update`] ./player/
+            // Damage is dealt here, the other modifiers are read where they apply
+            if let (Modifier::DamagePerSecond(typ), damage) = effect.modifier() {
+                if data.just_ticked_second(time.delta_seconds()) {
+                    damage_event.send(
+                        DamageEvent::new(None, entity, damage * (data.level as f32 + 1.))
+                            .with_type(typ),
+                    );
+                }
             }
         }
 
@@ -0,0 +1,91 @@
+use super::{buttons::scroll, make_menu, styles::text_style};
+use crate::{lang::Lang, player::death::RespawnEvent, save::CurrentSave, state::AppState};
+use bevy::prelude::*;
+
+pub struct DeathScreenPlugin;
+
+#[derive(Component)]
+pub struct DeathScreen;
+
+impl Plugin for DeathScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Dead), spawn_death_screen)
+            .add_systems(OnExit(AppState::Dead), despawn_death_screen)
+            .add_systems(
+                Update,
+                respawn_button_interact.run_if(in_state(AppState::Dead)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct RespawnButton;
+
+fn spawn_death_screen(
+    mut commands: Commands,
+    current_save: Res<CurrentSave>,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+) {
+    let death = current_save
+        .0
+        .as_ref()
+        .and_then(|save_data| save_data.read().deaths.last());
+
+    make_menu(
+        &mut commands,
+        Color::rgba(0.3, 0.0, 0.0, 0.5).into(),
+        DeathScreen,
+        |builder| {
+            builder.spawn(TextBundle::from_section(
+                lang.get("ui.death.title"),
+                TextStyle {
+                    font_size: 40.,
+                    ..text_style(&asset_server)
+                },
+            ));
+
+            if let Some(death) = death {
+                if death.money_lost > 0 {
+                    builder.spawn(TextBundle::from_section(
+                        format!("{} : {}", lang.get("ui.death.money_lost"), death.money_lost),
+                        text_style(&asset_server),
+                    ));
+                }
+
+                if death.items_dropped > 0 {
+                    builder.spawn(TextBundle::from_section(
+                        lang.get("ui.death.items_dropped"),
+                        text_style(&asset_server),
+                    ));
+                }
+            }
+
+            scroll::make_button(
+                builder,
+                lang.get("ui.death.respawn"),
+                RespawnButton,
+                &asset_server,
+            );
+        },
+        None,
+        None,
+    );
+}
+
+fn despawn_death_screen(mut commands: Commands, query: Query<Entity, With<DeathScreen>>) {
+    if let Ok(menu) = query.get_single() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
+
+fn respawn_button_interact(
+    query: Query<&Interaction, (With<RespawnButton>, Changed<Interaction>)>,
+    mut respawn_event: EventWriter<RespawnEvent>,
+) {
+    if let Ok(interaction) = query.get_single() {
+        if *interaction == Interaction::Pressed {
+            respawn_event.send(RespawnEvent)
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use bevy::prelude::*;
 
 use crate::{
+    boss::{Boss, BossTrait},
+    effects::{Effect, EffectsController},
     items::list::Item,
+    lang::Lang,
     player::{
         inventory::ui::{display_item_stack, display_slots, InventorySlot, UpdateSlotEvent},
-        mana::Mana,
         Player,
     },
     save::SaveData,
@@ -15,6 +17,7 @@ use crate::{
 use super::{
     main_menu::MainMenuState,
     settings::{keybinds::Keybind, Settings},
+    styles::text_style,
 };
 
 #[derive(Component)]
@@ -35,6 +38,29 @@ impl HudMana {
     pub const SIZE: f32 = InventorySlot::SIZE * 10.;
 }
 
+/// Health bar of the boss being fought, at the top of the screen
+#[derive(Component)]
+pub struct HudBossBar;
+
+#[derive(Component)]
+pub struct HudBossHealth;
+
+impl HudBossBar {
+    pub const SIZE: f32 = InventorySlot::SIZE * 16.;
+}
+
+/// Icons of the effects of the player, at the top left of the screen
+#[derive(Component)]
+pub struct HudEffects;
+
+impl HudEffects {
+    pub const ICON_SIZE: f32 = InventorySlot::SIZE;
+}
+
+/// Remaining time of an effect, under its icon
+#[derive(Component)]
+pub struct HudEffectTime(Effect);
+
 pub struct HudPlugin;
 impl Plugin for HudPlugin {
     fn build(&self, app: &mut App) {
@@ -45,7 +71,8 @@ impl Plugin for HudPlugin {
         .add_systems(OnEnter(AppState::InGame), spawn_hud)
         .add_systems(
             Update,
-            (update_hud, use_items).run_if(in_state(AppState::InGame)),
+            (update_hud, update_boss_bar, update_effects, use_items)
+                .run_if(in_state(AppState::InGame)),
         );
     }
 }
@@ -174,7 +201,9 @@ fn spawn_hud(
                                         background_color: Color::CYAN.into(),
                                         style: Style {
                                             width: Val::Percent(
-                                                player_data.player.mana.get() / Mana::MAX * 100.,
+                                                player_data.player.mana.get()
+                                                    / player_data.level.mana_capacity()
+                                                    * 100.,
                                             ),
                                             height: Val::Percent(100.),
                                             ..Default::default()
@@ -219,7 +248,7 @@ fn update_hud(
         }
 
         if let Ok(mut style) = mana_query.get_single_mut() {
-            style.width = Val::Percent(player.mana.get() / Mana::MAX * 100.);
+            style.width = Val::Percent(player.mana.get() / player.mana.max() * 100.);
         }
 
         for (heart, mut visibility, mut image) in heart_query.iter_mut() {
@@ -237,6 +266,179 @@ fn update_hud(
     }
 }
 
+fn update_boss_bar(
+    mut commands: Commands,
+    boss_query: Query<(&Boss, &Stats)>,
+    bar_query: Query<Entity, With<HudBossBar>>,
+    mut health_query: Query<&mut Style, With<HudBossHealth>>,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+) {
+    let boss = boss_query.iter().find(|(boss, _)| boss.is_fighting());
+
+    let (boss, stats) = match (boss, bar_query.get_single()) {
+        (Some(boss), _) => boss,
+        (None, Ok(bar)) => {
+            commands.entity(bar).despawn_recursive();
+            return;
+        }
+        (None, Err(_)) => return,
+    };
+
+    let health = stats.health.max(0.) / stats.max_health * 100.;
+    if let Ok(mut style) = health_query.get_single_mut() {
+        style.width = Val::Percent(health);
+        return;
+    }
+
+    commands
+        .spawn((Hud, HudBossBar))
+        .insert(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(24.),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            builder.spawn(TextBundle::from_section(
+                lang.get(&format!("boss.{}", boss.object.name())),
+                text_style(&asset_server),
+            ));
+
+            builder
+                .spawn(NodeBundle {
+                    background_color: Color::GRAY.into(),
+                    style: Style {
+                        width: Val::Px(HudBossBar::SIZE),
+                        height: Val::Px(InventorySlot::SIZE / 3.),
+                        padding: UiRect::all(Val::Px(2.)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|builder| {
+                    builder
+                        .spawn(NodeBundle {
+                            background_color: Color::CRIMSON.into(),
+                            style: Style {
+                                width: Val::Percent(health),
+                                height: Val::Percent(100.),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        })
+                        .insert(HudBossHealth);
+                });
+        });
+}
+
+fn update_effects(
+    mut commands: Commands,
+    player_query: Query<&EffectsController, With<Player>>,
+    container_query: Query<Entity, With<HudEffects>>,
+    mut time_query: Query<(&HudEffectTime, &mut Style)>,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok(effects) = player_query.get_single() else {
+        return;
+    };
+
+    let mut active: Vec<_> = effects.iter().collect();
+    if time_query.iter().count() == active.len()
+        && time_query
+            .iter()
+            .all(|(time, _)| effects.get_effect(&time.0).is_some())
+    {
+        for (time, mut style) in time_query.iter_mut() {
+            if let Some(data) = effects.get_effect(&time.0) {
+                style.width = Val::Percent(data.timer.percent_left() * 100.);
+            }
+        }
+        return;
+    }
+
+    // An effect started or ended, the icons are shown again
+    let container = match container_query.get_single() {
+        Ok(container) => {
+            commands.entity(container).despawn_descendants();
+            container
+        }
+        Err(_) => commands
+            .spawn((Hud, HudEffects))
+            .insert(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(24.),
+                    top: Val::Px(24.),
+                    display: Display::Flex,
+                    column_gap: Val::Px(8.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id(),
+    };
+
+    active.sort_by_key(|(effect, _)| effect.name());
+    commands.entity(container).with_children(|builder| {
+        for (effect, data) in active {
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(2.),
+                        width: Val::Px(HudEffects::ICON_SIZE),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|builder| {
+                    builder.spawn(ImageBundle {
+                        image: UiImage::new(asset_server.load(effect.icon())),
+                        style: Style {
+                            width: Val::Px(HudEffects::ICON_SIZE),
+                            height: Val::Px(HudEffects::ICON_SIZE),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    });
+
+                    builder
+                        .spawn(NodeBundle {
+                            background_color: Color::GRAY.into(),
+                            style: Style {
+                                width: Val::Percent(100.),
+                                height: Val::Px(HudEffects::ICON_SIZE / 6.),
+                                padding: UiRect::all(Val::Px(1.)),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        })
+                        .with_children(|builder| {
+                            builder
+                                .spawn(NodeBundle {
+                                    background_color: Color::WHITE.into(),
+                                    style: Style {
+                                        width: Val::Percent(data.timer.percent_left() * 100.),
+                                        height: Val::Percent(100.),
+                                        ..Default::default()
+                                    },
+                                    ..Default::default()
+                                })
+                                .insert(HudEffectTime(effect.clone()));
+                        });
+                });
+        }
+    });
+}
+
 fn despawn_hud(mut commands: Commands, query: Query<Entity, With<Hud>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
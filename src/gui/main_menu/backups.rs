@@ -0,0 +1,177 @@
+use crate::{
+    gui::{buttons::scroll::make_button, make_menu, styles::text_style},
+    lang::Lang,
+    save::Save,
+    state::AppState,
+};
+use bevy::prelude::*;
+
+use super::MainMenuState;
+
+pub struct BackupsMenuPlugin;
+impl Plugin for BackupsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BackupsMenuSave>()
+            .add_systems(
+                Update,
+                (back_button, restore_button)
+                    .run_if(in_state(AppState::MainMenu(MainMenuState::Backups))),
+            )
+            .add_systems(
+                OnEnter(AppState::MainMenu(MainMenuState::Backups)),
+                spawn_backups_menu,
+            )
+            .add_systems(
+                OnExit(AppState::MainMenu(MainMenuState::Backups)),
+                despawn_backups_menu,
+            );
+    }
+}
+
+/// Name of the save whose backups are listed
+#[derive(Resource, Default)]
+pub struct BackupsMenuSave(pub Option<String>);
+
+#[derive(Component)]
+pub struct BackupsMenu;
+
+#[derive(Component)]
+pub struct BackupsBackButton;
+
+#[derive(Component)]
+pub struct RestoreBackupButton {
+    pub index: usize,
+}
+
+fn spawn_backups_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+    backups_menu_save: Res<BackupsMenuSave>,
+) {
+    let backups = backups_menu_save
+        .0
+        .as_ref()
+        .map(|name| Save::get_backups(name))
+        .unwrap_or_default();
+
+    make_menu(
+        &mut commands,
+        Color::BLACK.into(),
+        BackupsMenu,
+        |builder| {
+            if !backups.is_empty() {
+                builder
+                    .spawn(NodeBundle {
+                        style: Style {
+                            height: Val::Percent(70.),
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Column,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|builder| {
+                        for (index, date) in backups {
+                            backup_item(
+                                builder,
+                                index,
+                                &date.format("%d/%m/%Y %H:%M").to_string(),
+                                &asset_server,
+                                &lang,
+                            );
+                        }
+                    });
+            } else {
+                builder.spawn(TextBundle::from_section(
+                    lang.get("ui.main_menu.backups.none"),
+                    text_style(&asset_server),
+                ));
+            }
+
+            make_button(
+                builder,
+                lang.get("ui.main_menu.backups.back"),
+                BackupsBackButton,
+                &asset_server,
+            );
+        },
+        None,
+        None,
+    )
+}
+
+fn backup_item(
+    builder: &mut ChildBuilder,
+    index: usize,
+    date: &str,
+    asset_server: &Res<AssetServer>,
+    lang: &Res<Lang>,
+) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                height: Val::Percent(15.),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            builder.spawn(TextBundle::from_section(date, text_style(asset_server)));
+
+            make_button(
+                builder,
+                lang.get("ui.main_menu.backups.restore"),
+                RestoreBackupButton { index },
+                asset_server,
+            );
+        });
+}
+
+fn back_button(
+    query: Query<&Interaction, With<BackupsBackButton>>,
+    mut state_change: ResMut<NextState<AppState>>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            state_change.set(AppState::MainMenu(MainMenuState::LoadWorld))
+        }
+    }
+}
+
+fn restore_button(
+    query: Query<(&Interaction, &RestoreBackupButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    backups_menu_save: Res<BackupsMenuSave>,
+    mut state_change: ResMut<NextState<AppState>>,
+) {
+    let Some(save_name) = &backups_menu_save.0 else {
+        return;
+    };
+
+    for (interaction, button, children) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            match Save::restore_backup(save_name, button.index) {
+                Ok(()) => state_change.set(AppState::MainMenu(MainMenuState::LoadWorld)),
+                Err(e) => {
+                    for child in children {
+                        if let Ok(mut text) = text_query.get_mut(*child) {
+                            text.sections[0].style.color = Color::RED;
+                        }
+                    }
+                    error!("{e}")
+                }
+            }
+        }
+    }
+}
+
+fn despawn_backups_menu(mut commands: Commands, menu_query: Query<Entity, With<BackupsMenu>>) {
+    for menu in menu_query.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
@@ -1,19 +1,29 @@
 use crate::{
     gui::{buttons::scroll::make_button, make_menu, styles::text_style},
     lang::Lang,
+    mob::definition::mob_definitions_loaded,
     save::{Save, SaveData, SaveMetaData},
     state::AppState,
 };
 use bevy::prelude::*;
 
-use super::MainMenuState;
+use super::{backups::BackupsMenuSave, manage_world::ManagedWorld, MainMenuState};
 
 pub struct LoadWorldMenuPlugin;
 impl Plugin for LoadWorldMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (back_button, load_button, delete_button)
+            (
+                back_button,
+                load_button.run_if(mob_definitions_loaded),
+                backups_button,
+                export_button,
+                import_button,
+                rename_button,
+                duplicate_button,
+                delete_button,
+            )
                 .run_if(in_state(AppState::MainMenu(MainMenuState::LoadWorld))),
         )
         .add_systems(
@@ -38,14 +48,45 @@ pub struct LoadWorldButton {
     pub save_name: String,
 }
 
+#[derive(Component)]
+pub struct BackupsWorldButton {
+    pub save_name: String,
+}
+
+#[derive(Component)]
+pub struct ExportWorldButton {
+    pub save_name: String,
+}
+
+#[derive(Component)]
+pub struct ImportWorldsButton;
+
+#[derive(Component)]
+pub struct RenameWorldButton {
+    pub save_name: String,
+}
+
+#[derive(Component)]
+pub struct DuplicateWorldButton {
+    pub save_name: String,
+}
+
 #[derive(Component)]
 pub struct DeleteWorldButton {
     pub save_name: String,
 }
 
 fn spawn_load_world_menu(mut commands: Commands, asset_server: Res<AssetServer>, lang: Res<Lang>) {
+    make_load_world_menu(&mut commands, &asset_server, &lang)
+}
+
+fn make_load_world_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    lang: &Res<Lang>,
+) {
     make_menu(
-        &mut commands,
+        commands,
         Color::BLACK.into(),
         LoadWorldMenu,
         |builder| {
@@ -65,21 +106,38 @@ fn spawn_load_world_menu(mut commands: Commands, asset_server: Res<AssetServer>,
                     .with_children(|builder| {
                         saves
                             .into_iter()
-                            .for_each(|data| world_save_item(builder, data, &asset_server, &lang));
+                            .for_each(|data| world_save_item(builder, data, asset_server, lang));
                     });
             } else {
                 builder.spawn(TextBundle::from_section(
                     lang.get("ui.main_menu.load_save.none"),
-                    text_style(&asset_server),
+                    text_style(asset_server),
                 ));
             }
 
-            make_button(
-                builder,
-                lang.get("ui.main_menu.load_save.back"),
-                LoadWorldBackButton,
-                &asset_server,
-            );
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Row,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|builder| {
+                    make_button(
+                        builder,
+                        lang.get("ui.main_menu.load_save.import"),
+                        ImportWorldsButton,
+                        asset_server,
+                    );
+                    make_button(
+                        builder,
+                        lang.get("ui.main_menu.load_save.back"),
+                        LoadWorldBackButton,
+                        asset_server,
+                    );
+                });
         },
         None,
         None,
@@ -139,6 +197,42 @@ fn world_save_item(
                     asset_server,
                 );
 
+                make_button(
+                    builder,
+                    lang.get("ui.main_menu.load_save.backups"),
+                    BackupsWorldButton {
+                        save_name: save_name.clone(),
+                    },
+                    asset_server,
+                );
+
+                make_button(
+                    builder,
+                    lang.get("ui.main_menu.load_save.export"),
+                    ExportWorldButton {
+                        save_name: save_name.clone(),
+                    },
+                    asset_server,
+                );
+
+                make_button(
+                    builder,
+                    lang.get("ui.main_menu.load_save.rename"),
+                    RenameWorldButton {
+                        save_name: save_name.clone(),
+                    },
+                    asset_server,
+                );
+
+                make_button(
+                    builder,
+                    lang.get("ui.main_menu.load_save.duplicate"),
+                    DuplicateWorldButton {
+                        save_name: save_name.clone(),
+                    },
+                    asset_server,
+                );
+
                 make_button(
                     builder,
                     lang.get("ui.main_menu.load_save.delete"),
@@ -179,20 +273,116 @@ fn load_button(
                             text.sections[0].style.color = Color::RED;
                         }
                     }
-                    error!(e)
+                    error!("{e}")
                 }
             }
         }
     }
 }
 
-fn delete_button(
+fn backups_button(
+    query: Query<(&Interaction, &BackupsWorldButton), Changed<Interaction>>,
+    mut backups_menu_save: ResMut<BackupsMenuSave>,
+    mut state_change: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            backups_menu_save.0 = Some(button.save_name.clone());
+            state_change.set(AppState::MainMenu(MainMenuState::Backups))
+        }
+    }
+}
+
+fn export_button(
+    query: Query<(&Interaction, &ExportWorldButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (interaction, button, children) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            match Save::export_default(&button.save_name) {
+                Ok(path) => info!("Exported {} to {}", button.save_name, path.display()),
+                Err(e) => {
+                    for child in children {
+                        if let Ok(mut text) = text_query.get_mut(*child) {
+                            text.sections[0].style.color = Color::RED;
+                        }
+                    }
+                    error!("{e}")
+                }
+            }
+        }
+    }
+}
+
+fn import_button(
+    mut commands: Commands,
+    query: Query<&Interaction, (With<ImportWorldsButton>, Changed<Interaction>)>,
+    menu_query: Query<Entity, With<LoadWorldMenu>>,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            for res in Save::import_all() {
+                match res {
+                    Ok(name) => info!("Imported world {name}"),
+                    Err(e) => error!("Failed to import world : {e}"),
+                }
+            }
+
+            // Respawn the menu to list the imported worlds
+            for menu in menu_query.iter() {
+                commands.entity(menu).despawn_recursive();
+            }
+            make_load_world_menu(&mut commands, &asset_server, &lang);
+        }
+    }
+}
+
+fn rename_button(
+    query: Query<(&Interaction, &RenameWorldButton), Changed<Interaction>>,
+    mut managed_world: ResMut<ManagedWorld>,
+    mut state_change: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            managed_world.0 = Some(button.save_name.clone());
+            state_change.set(AppState::MainMenu(MainMenuState::RenameWorld))
+        }
+    }
+}
+
+fn duplicate_button(
     mut commands: Commands,
-    query: Query<(&Parent, &Interaction, &DeleteWorldButton)>,
+    query: Query<(&Interaction, &DuplicateWorldButton), Changed<Interaction>>,
+    menu_query: Query<Entity, With<LoadWorldMenu>>,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
 ) {
-    for (parent, interaction, button) in query.iter() {
-        if *interaction == Interaction::Pressed && Save::delete(&button.save_name).is_ok() {
-            commands.entity(parent.get()).despawn_recursive();
+    for (interaction, button) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            match Save::duplicate(&button.save_name) {
+                Ok(name) => info!("Duplicated world {} into {name}", button.save_name),
+                Err(e) => error!("Failed to duplicate world : {e}"),
+            }
+
+            for menu in menu_query.iter() {
+                commands.entity(menu).despawn_recursive();
+            }
+            make_load_world_menu(&mut commands, &asset_server, &lang);
+        }
+    }
+}
+
+fn delete_button(
+    query: Query<(&Interaction, &DeleteWorldButton), Changed<Interaction>>,
+    mut managed_world: ResMut<ManagedWorld>,
+    mut state_change: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            managed_world.0 = Some(button.save_name.clone());
+            state_change.set(AppState::MainMenu(MainMenuState::DeleteWorld))
         }
     }
 }
@@ -0,0 +1,279 @@
+use crate::{
+    gui::{buttons::scroll::make_button, make_menu, styles::text_style},
+    lang::Lang,
+    save::{Save, SaveError},
+    state::AppState,
+};
+use bevy::prelude::*;
+use bevy_simple_text_input::TextInput;
+
+use super::MainMenuState;
+
+/// Menus to rename a world or confirm its deletion
+pub struct ManageWorldMenuPlugin;
+impl Plugin for ManageWorldMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ManagedWorld>()
+            .add_systems(
+                Update,
+                (cancel_button, rename_confirm_button)
+                    .run_if(in_state(AppState::MainMenu(MainMenuState::RenameWorld))),
+            )
+            .add_systems(
+                Update,
+                (cancel_button, delete_confirm_button)
+                    .run_if(in_state(AppState::MainMenu(MainMenuState::DeleteWorld))),
+            )
+            .add_systems(
+                OnEnter(AppState::MainMenu(MainMenuState::RenameWorld)),
+                spawn_rename_world_menu,
+            )
+            .add_systems(
+                OnEnter(AppState::MainMenu(MainMenuState::DeleteWorld)),
+                spawn_delete_world_menu,
+            )
+            .add_systems(
+                OnExit(AppState::MainMenu(MainMenuState::RenameWorld)),
+                despawn_manage_world_menu,
+            )
+            .add_systems(
+                OnExit(AppState::MainMenu(MainMenuState::DeleteWorld)),
+                despawn_manage_world_menu,
+            );
+    }
+}
+
+/// Name of the save being renamed or deleted
+#[derive(Resource, Default)]
+pub struct ManagedWorld(pub Option<String>);
+
+#[derive(Component)]
+pub struct ManageWorldMenu;
+
+#[derive(Component)]
+pub struct ManageWorldCancelButton;
+
+#[derive(Component)]
+pub struct RenameConfirmButton;
+
+#[derive(Component)]
+pub struct DeleteConfirmButton;
+
+#[derive(Component)]
+pub struct RenameInput;
+
+fn spawn_rename_world_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+    managed_world: Res<ManagedWorld>,
+) {
+    let name = managed_world.0.clone().unwrap_or_default();
+
+    make_menu(
+        &mut commands,
+        Color::BLACK.into(),
+        ManageWorldMenu,
+        |builder| {
+            builder.spawn(TextBundle::from_section(
+                format!("{} : {name}", lang.get("ui.main_menu.manage_world.rename")),
+                text_style(&asset_server),
+            ));
+
+            builder.spawn((
+                RenameInput,
+                NodeBundle {
+                    style: Style {
+                        width: Val::Px(500.0),
+                        border: UiRect::all(Val::Px(5.0)),
+                        padding: UiRect::all(Val::Px(5.0)),
+                        margin: UiRect::all(Val::Px(5.0)),
+                        ..Default::default()
+                    },
+                    border_color: BorderColor(Color::WHITE),
+                    background_color: Color::GRAY.into(),
+                    ..Default::default()
+                },
+                TextInput {
+                    text_style: TextStyle {
+                        font_size: 40.,
+                        ..text_style(&asset_server)
+                    },
+                    ..Default::default()
+                },
+            ));
+
+            confirm_row(
+                builder,
+                lang.get("ui.main_menu.manage_world.rename"),
+                RenameConfirmButton,
+                &asset_server,
+                &lang,
+            );
+        },
+        None,
+        None,
+    )
+}
+
+fn spawn_delete_world_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+    managed_world: Res<ManagedWorld>,
+) {
+    let name = managed_world.0.clone().unwrap_or_default();
+
+    make_menu(
+        &mut commands,
+        Color::BLACK.into(),
+        ManageWorldMenu,
+        |builder| {
+            builder.spawn(TextBundle::from_section(
+                format!(
+                    "{} {name} ?",
+                    lang.get("ui.main_menu.manage_world.confirm_delete")
+                ),
+                text_style(&asset_server),
+            ));
+
+            builder.spawn(TextBundle::from_section(
+                lang.get("ui.main_menu.manage_world.irreversible"),
+                TextStyle {
+                    color: Color::RED,
+                    ..text_style(&asset_server)
+                },
+            ));
+
+            confirm_row(
+                builder,
+                lang.get("ui.main_menu.load_save.delete"),
+                DeleteConfirmButton,
+                &asset_server,
+                &lang,
+            );
+        },
+        None,
+        None,
+    )
+}
+
+fn confirm_row<T: Component>(
+    builder: &mut ChildBuilder,
+    confirm_text: &str,
+    confirm_button: T,
+    asset_server: &Res<AssetServer>,
+    lang: &Res<Lang>,
+) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Row,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            make_button(
+                builder,
+                lang.get("ui.main_menu.manage_world.cancel"),
+                ManageWorldCancelButton,
+                asset_server,
+            );
+            make_button(builder, confirm_text, confirm_button, asset_server);
+        });
+}
+
+fn cancel_button(
+    query: Query<&Interaction, With<ManageWorldCancelButton>>,
+    mut state_change: ResMut<NextState<AppState>>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            state_change.set(AppState::MainMenu(MainMenuState::LoadWorld))
+        }
+    }
+}
+
+fn rename_confirm_button(
+    query: Query<&Interaction, (With<RenameConfirmButton>, Changed<Interaction>)>,
+    mut input_query: Query<(&Children, &mut BorderColor), With<RenameInput>>,
+    children_query: Query<&Children>,
+    text_query: Query<&Text>,
+    managed_world: Res<ManagedWorld>,
+    mut state_change: ResMut<NextState<AppState>>,
+) {
+    let Some(save_name) = &managed_world.0 else {
+        return;
+    };
+
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            let Ok((children, mut border_color)) = input_query.get_single_mut() else {
+                continue;
+            };
+
+            for child in children.iter() {
+                let Ok(inner_children) = children_query.get(*child) else {
+                    continue;
+                };
+
+                for inner_child in inner_children.iter() {
+                    let Ok(text) = text_query.get(*inner_child) else {
+                        continue;
+                    };
+
+                    let new_name = format!("{}{}", text.sections[0].value, text.sections[2].value);
+
+                    match Save::rename(save_name, new_name.trim()) {
+                        Ok(()) => state_change.set(AppState::MainMenu(MainMenuState::LoadWorld)),
+                        Err(SaveError::InvalidName | SaveError::AlreadyExists) => {
+                            *border_color = BorderColor(Color::RED)
+                        }
+                        Err(e) => {
+                            *border_color = BorderColor(Color::RED);
+                            error!("{e}")
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn delete_confirm_button(
+    query: Query<(&Interaction, &Children), (With<DeleteConfirmButton>, Changed<Interaction>)>,
+    mut text_query: Query<&mut Text>,
+    managed_world: Res<ManagedWorld>,
+    mut state_change: ResMut<NextState<AppState>>,
+) {
+    let Some(save_name) = &managed_world.0 else {
+        return;
+    };
+
+    for (interaction, children) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            match Save::delete(save_name) {
+                Ok(()) => state_change.set(AppState::MainMenu(MainMenuState::LoadWorld)),
+                Err(e) => {
+                    for child in children {
+                        if let Ok(mut text) = text_query.get_mut(*child) {
+                            text.sections[0].style.color = Color::RED;
+                        }
+                    }
+                    error!("{e}")
+                }
+            }
+        }
+    }
+}
+
+fn despawn_manage_world_menu(
+    mut commands: Commands,
+    menu_query: Query<Entity, With<ManageWorldMenu>>,
+) {
+    for menu in menu_query.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
@@ -1,4 +1,7 @@
-use self::{load_world::LoadWorldMenuPlugin, new_world::NewWorldMenuPlugin};
+use self::{
+    backups::BackupsMenuPlugin, load_world::LoadWorldMenuPlugin,
+    manage_world::ManageWorldMenuPlugin, new_world::NewWorldMenuPlugin,
+};
 
 use super::{buttons::scroll::make_button, make_menu, settings::ui::settings_button};
 use crate::{lang::Lang, state::AppState};
@@ -6,7 +9,9 @@ use bevy::{app::AppExit, prelude::*};
 use rand::{seq::SliceRandom, thread_rng};
 use std::path::Path;
 
+pub mod backups;
 pub mod load_world;
+pub mod manage_world;
 pub mod new_world;
 
 const BACKGROUNDS: [&str; 2] = ["montagnes.png", "plaines.png"];
@@ -18,6 +23,9 @@ pub enum MainMenuState {
     Default,
     NewWorld,
     LoadWorld,
+    Backups,
+    RenameWorld,
+    DeleteWorld,
 }
 
 impl Plugin for MainMenuPlugin {
@@ -39,7 +47,12 @@ impl Plugin for MainMenuPlugin {
             OnExit(AppState::MainMenu(MainMenuState::Default)),
             despawn_main_menu,
         )
-        .add_plugins((NewWorldMenuPlugin, LoadWorldMenuPlugin));
+        .add_plugins((
+            NewWorldMenuPlugin,
+            LoadWorldMenuPlugin,
+            BackupsMenuPlugin,
+            ManageWorldMenuPlugin,
+        ));
     }
 }
 
@@ -5,7 +5,9 @@ use strum::{EnumCount, IntoEnumIterator};
 use crate::{
     gui::{buttons::scroll::make_button, make_menu, misc::PIXEL_FONT, styles::text_style},
     lang::Lang,
+    mob::definition::mob_definitions_loaded,
     player::class::{PlayerClass, PlayerClasses},
+    random::WorldRng,
     save::{Save, SaveData},
     state::AppState,
 };
@@ -17,7 +19,12 @@ impl Plugin for NewWorldMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (cancel_button, start_button, update_selected_class)
+            (
+                cancel_button,
+                start_button.run_if(mob_definitions_loaded),
+                update_selected_class,
+                focus_input,
+            )
                 .run_if(in_state(AppState::MainMenu(MainMenuState::NewWorld))),
         )
         .add_systems(
@@ -49,6 +56,9 @@ pub struct NewWorldStartButton;
 #[derive(Component)]
 pub struct WorldNameInput;
 
+#[derive(Component)]
+pub struct WorldSeedInput;
+
 #[derive(Component)]
 pub struct ArrowRight;
 
@@ -71,24 +81,30 @@ fn spawn_new_world_menu(
         |builder| {
             builder.spawn((
                 WorldNameInput,
-                NodeBundle {
-                    style: Style {
-                        width: Val::Px(500.0),
-                        border: UiRect::all(Val::Px(5.0)),
-                        padding: UiRect::all(Val::Px(5.0)),
-                        margin: UiRect::all(Val::Px(5.0)),
-                        ..Default::default()
+                input_node(),
+                TextInput {
+                    text_style: TextStyle {
+                        font_size: 40.,
+                        ..text_style(&asset_server)
                     },
-                    border_color: BorderColor(Color::WHITE),
-                    background_color: Color::GRAY.into(),
                     ..Default::default()
                 },
+            ));
+
+            builder.spawn(TextBundle::from_section(
+                lang.get("ui.main_menu.new_world.seed"),
+                text_style(&asset_server),
+            ));
+
+            builder.spawn((
+                WorldSeedInput,
+                input_node(),
                 TextInput {
                     text_style: TextStyle {
-                        font_size: 40.,
+                        font_size: 24.,
                         ..text_style(&asset_server)
                     },
-                    ..Default::default()
+                    inactive: true,
                 },
             ));
 
@@ -184,6 +200,25 @@ fn spawn_new_world_menu(
     )
 }
 
+/// Box of a [`TextInput`], clicking it moves the focus to this input
+fn input_node() -> (NodeBundle, Interaction) {
+    (
+        NodeBundle {
+            style: Style {
+                width: Val::Px(500.0),
+                border: UiRect::all(Val::Px(5.0)),
+                padding: UiRect::all(Val::Px(5.0)),
+                margin: UiRect::all(Val::Px(5.0)),
+                ..Default::default()
+            },
+            border_color: BorderColor(Color::WHITE),
+            background_color: Color::GRAY.into(),
+            ..Default::default()
+        },
+        Interaction::default(),
+    )
+}
+
 fn text_from_class(
     lang: &Res<Lang>,
     selected_class: &CurrentSelectedClass,
@@ -305,9 +340,39 @@ fn cancel_button(
     }
 }
 
+fn focus_input(mut query: Query<(Entity, &Interaction, &mut TextInput)>) {
+    let Some(focused) = query
+        .iter()
+        .find(|(_, interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(entity, _, _)| entity)
+    else {
+        return;
+    };
+
+    for (entity, _, mut input) in query.iter_mut() {
+        input.inactive = entity != focused;
+    }
+}
+
+/// # Returns
+/// What was typed in the [`TextInput`] with the given children
+fn input_text(
+    children: &Children,
+    children_query: &Query<&Children>,
+    text_query: &Query<&Text>,
+) -> Option<String> {
+    children
+        .iter()
+        .filter_map(|child| children_query.get(*child).ok())
+        .flat_map(|inner_children| inner_children.iter())
+        .find_map(|inner_child| text_query.get(*inner_child).ok())
+        .map(|text| format!("{}{}", text.sections[0].value, text.sections[2].value))
+}
+
 fn start_button(
     query: Query<&Interaction, (With<NewWorldStartButton>, Changed<Interaction>)>,
     mut input_query: Query<(&Children, &mut BorderColor), With<WorldNameInput>>,
+    seed_query: Query<&Children, With<WorldSeedInput>>,
     children_query: Query<&Children>,
     text_query: Query<&Text>,
     mut state_change: ResMut<NextState<AppState>>,
@@ -320,29 +385,25 @@ fn start_button(
                 continue;
             };
 
-            for child in children.iter() {
-                let Ok(inner_children) = children_query.get(*child) else {
-                    continue;
-                };
-
-                for inner_child in inner_children.iter() {
-                    let Ok(text) = text_query.get(*inner_child) else {
-                        continue;
-                    };
+            let Some(save_name) = input_text(children, &children_query, &text_query) else {
+                continue;
+            };
 
-                    let save_name = format!("{}{}", text.sections[0].value, text.sections[2].value);
+            let seed = seed_query
+                .get_single()
+                .ok()
+                .and_then(|children| input_text(children, &children_query, &text_query))
+                .map(|text| WorldRng::seed_from_text(&text))
+                .unwrap_or_else(WorldRng::random_seed);
 
-                    let class =
-                        PlayerClasses::iter().collect::<Vec<_>>()[selected_class.index].clone();
+            let class = PlayerClasses::iter().collect::<Vec<_>>()[selected_class.index].clone();
 
-                    match Save::new(&save_name, class) {
-                        Ok((save, meta)) => {
-                            state_change.set(AppState::InGame);
-                            save_event.send(SaveData::new(&meta.name, save))
-                        }
-                        Err(_) => *border_color = BorderColor(Color::RED),
-                    }
+            match Save::new(&save_name, class, seed) {
+                Ok((save, meta)) => {
+                    state_change.set(AppState::InGame);
+                    save_event.send(SaveData::new(&meta.name, save))
                 }
+                Err(_) => *border_color = BorderColor(Color::RED),
             }
         }
     }
@@ -1,11 +1,13 @@
 use self::{
-    buttons::scroll::button_interact, hud::HudPlugin, main_menu::MainMenuPlugin, misc::Background,
-    pause::PausePlugin, settings::SettingsPlugin, slider::SliderPlugin, styles::aligned_center,
+    buttons::scroll::button_interact, death::DeathScreenPlugin, hud::HudPlugin,
+    main_menu::MainMenuPlugin, misc::Background, pause::PausePlugin, settings::SettingsPlugin,
+    slider::SliderPlugin, styles::aligned_center,
 };
 use bevy::prelude::*;
 use bevy_simple_text_input::TextInputPlugin;
 
 pub mod buttons;
+pub mod death;
 pub mod hud;
 pub mod main_menu;
 pub mod misc;
@@ -23,6 +25,7 @@ impl Plugin for GuiPlugin {
             SliderPlugin,
             SettingsPlugin,
             HudPlugin,
+            DeathScreenPlugin,
         ))
         .add_systems(Update, button_interact)
         .add_plugins(TextInputPlugin);
@@ -1,5 +1,5 @@
 use super::{buttons::scroll, main_menu::MainMenuState, make_menu, settings::ui::settings_button};
-use crate::{lang::Lang, state::AppState};
+use crate::{lang::Lang, save::SaveWorldEvent, state::AppState};
 use bevy::prelude::*;
 
 pub struct PausePlugin;
@@ -13,7 +13,12 @@ impl Plugin for PausePlugin {
             .add_systems(OnExit(AppState::Paused), despawn_pause_menu)
             .add_systems(
                 Update,
-                (resume_button_interact, leave_button_interact).run_if(in_state(AppState::Paused)),
+                (
+                    resume_button_interact,
+                    save_button_interact,
+                    leave_button_interact,
+                )
+                    .run_if(in_state(AppState::Paused)),
             );
     }
 }
@@ -21,6 +26,9 @@ impl Plugin for PausePlugin {
 #[derive(Component)]
 struct ResumeButton;
 
+#[derive(Component)]
+struct SaveButton;
+
 #[derive(Component)]
 struct LeaveButton;
 
@@ -36,6 +44,12 @@ fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>, lang
                 ResumeButton,
                 &asset_server,
             );
+            scroll::make_button(
+                builder,
+                lang.get("ui.pause.save"),
+                SaveButton,
+                &asset_server,
+            );
             settings_button(builder, &asset_server, &lang);
             scroll::make_button(
                 builder,
@@ -66,6 +80,17 @@ fn resume_button_interact(
     }
 }
 
+fn save_button_interact(
+    query: Query<&Interaction, (With<SaveButton>, Changed<Interaction>)>,
+    mut save_event: EventWriter<SaveWorldEvent>,
+) {
+    if let Ok(interaction) = query.get_single() {
+        if *interaction == Interaction::Pressed {
+            save_event.send(SaveWorldEvent)
+        }
+    }
+}
+
 fn leave_button_interact(
     query: Query<&Interaction, With<LeaveButton>>,
     mut state_next_state: ResMut<NextState<AppState>>,
@@ -38,9 +38,7 @@ pub fn lang_chooser(
             for lang in LangIdentifier::iter() {
                 let lang: Lang = lang.into();
                 builder
-                    .spawn(LangChooseButton {
-                        ident: lang.ident.clone(),
-                    })
+                    .spawn(LangChooseButton { ident: lang.ident })
                     .insert(ButtonBundle {
                         border_color: BorderColor(Color::WHITE),
                         background_color: Color::NONE.into(),
@@ -76,7 +74,7 @@ pub fn lang_choose_buttons_update(
 ) {
     for (interaction, button, mut style) in query.iter_mut() {
         if *interaction == Interaction::Pressed {
-            settings.update_lang(button.ident.clone(), &mut lang_res)
+            settings.update_lang(button.ident, &mut lang_res)
         }
 
         style.border = UiRect::all(Val::Px(if button.ident == settings.lang {
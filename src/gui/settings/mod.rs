@@ -15,6 +15,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     lang::{Lang, LangIdentifier},
+    player::death::DeathPenalty,
     CONFIG_DIR,
 };
 
@@ -28,6 +29,8 @@ pub struct Settings {
     pub keybinds: Keybinds,
     pub lang: LangIdentifier,
     pub audio: AudioChannelsVolumeRanges,
+    #[serde(default)]
+    pub death_penalty: DeathPenalty,
 }
 
 impl Settings {
@@ -67,7 +70,7 @@ impl Settings {
     where
         F: Fn(&mut Self),
     {
-        let old_lang = self.lang.clone();
+        let old_lang = self.lang;
         callback(self);
         if self.lang != old_lang {
             // Loading into ressources is required for lang
@@ -77,7 +80,7 @@ impl Settings {
     }
 
     pub fn update_lang(&mut self, lang_indent: LangIdentifier, lang_res: &mut ResMut<Lang>) {
-        let lang: Lang = lang_indent.clone().into();
+        let lang: Lang = lang_indent.into();
         **lang_res = lang.load();
         self.lang = lang_indent;
         self.save().unwrap()
@@ -87,17 +90,18 @@ impl Settings {
 impl Default for Settings {
     fn default() -> Self {
         Self {
-            lang: LangIdentifier::default().into(),
+            lang: LangIdentifier::default(),
             fov: FovRange { value: 40.0 },
             keybinds: Keybinds::default(),
             audio: AudioChannelsVolumeRanges::default(),
+            death_penalty: DeathPenalty::default(),
         }
     }
 }
 
 fn load_settings(mut commands: Commands) {
     let settings = Settings::read();
-    let lang: Lang = settings.lang.clone().into();
+    let lang: Lang = settings.lang.into();
     commands.insert_resource(lang.load());
     commands.insert_resource(settings);
 }
@@ -121,13 +121,9 @@ pub fn update_slider_value(
             if let Some(cursor_position) = cursor_position.normalized {
                 // Get the slider handle node
                 let slider_handle_node = if let Some(children) = children {
-                    children.iter().find_map(|child| {
-                        if let Ok(node) = slider_handle_query.get(*child) {
-                            Some(node)
-                        } else {
-                            None
-                        }
-                    })
+                    children
+                        .iter()
+                        .find_map(|child| slider_handle_query.get(*child).ok())
                 } else {
                     None
                 };
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     animation::AnimationController,
+    effects::Effect,
     gui::hud::UseItemEvent,
     interactable::Interactable,
     player::{
@@ -49,6 +50,21 @@ pub trait ItemTrait: Sync + Send + Reflect {
     fn use_item(&self) -> bool {
         false
     }
+
+    /// Strength given while worn in an armor or accessory slot
+    fn strength(&self) -> f32 {
+        0.
+    }
+
+    /// Defence given while worn in an armor or accessory slot
+    fn def(&self) -> f32 {
+        0.
+    }
+
+    /// Given to the player using the item, for some seconds at some level
+    fn effect(&self) -> Option<(Effect, f32, u8)> {
+        None
+    }
 }
 
 pub struct ItemTexture(String);
@@ -65,6 +81,12 @@ impl From<ItemName> for ItemTexture {
     }
 }
 
+impl ItemTexture {
+    pub fn get(&self) -> &str {
+        &self.0
+    }
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct ItemName(String);
 
@@ -5,7 +5,7 @@ use crate::{
     animation::AnimationController, gui::hud::UseItemEvent, items::item::ItemTrait, player::Player,
 };
 
-#[derive(Clone, Deserialize, Serialize, Reflect, PartialEq, Eq, Default)]
+#[derive(Clone, Deserialize, Serialize, Reflect, PartialEq, Eq, Default, Debug)]
 pub struct ManaPotion;
 
 impl ItemTrait for ManaPotion {
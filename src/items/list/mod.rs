@@ -1,6 +1,11 @@
 use bevy::{
     app::{Plugin, Update},
-    ecs::schedule::{common_conditions::in_state, IntoSystemConfigs},
+    ecs::{
+        event::EventReader,
+        query::With,
+        schedule::{common_conditions::in_state, IntoSystemConfigs},
+        system::Query,
+    },
     reflect::Reflect,
 };
 use enum_dispatch::enum_dispatch;
@@ -8,21 +13,37 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use strum_macros::EnumString;
 
-use crate::{player::inventory::SlotType, state::AppState};
+use crate::{
+    animation::AnimationController,
+    effects::{Effect, EffectsController},
+    gui::hud::UseItemEvent,
+    player::{inventory::SlotType, Player},
+    state::AppState,
+};
 
 use self::{
-    levitation_potion::{use_levitation_potion, LevitationPotion},
+    jump_potion::JumpPotion,
+    levitation_potion::LevitationPotion,
     mana_potion::{use_mana_potion, ManaPotion},
     porkchop::Porkchop,
     processed_ore::ProcessedOre,
+    regeneration_potion::RegenerationPotion,
+    resistance_potion::ResistancePotion,
+    strength_potion::StrengthPotion,
+    swiftness_potion::SwiftnessPotion,
     unprocessed_ore::UnprocessedOre,
 };
 use super::item::{ItemName, ItemTrait, StackSize};
 
+pub mod jump_potion;
 pub mod levitation_potion;
 pub mod mana_potion;
 pub mod porkchop;
 pub mod processed_ore;
+pub mod regeneration_potion;
+pub mod resistance_potion;
+pub mod strength_potion;
+pub mod swiftness_potion;
 pub mod unprocessed_ore;
 
 pub struct ItemsPlugin;
@@ -30,12 +51,12 @@ impl Plugin for ItemsPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.add_systems(
             Update,
-            (use_mana_potion, use_levitation_potion).run_if(in_state(AppState::InGame)),
+            (use_mana_potion, use_effect_items).run_if(in_state(AppState::InGame)),
         );
     }
 }
 
-#[derive(Clone, Deserialize, Serialize, Reflect, PartialEq, Eq, EnumString)]
+#[derive(Clone, Deserialize, Serialize, Reflect, PartialEq, Eq, EnumString, Debug)]
 #[enum_dispatch(ItemTrait)]
 pub enum Item {
     ManaPotion(ManaPotion),
@@ -43,4 +64,24 @@ pub enum Item {
     Porkchop(Porkchop),
     UnprocessedOre(UnprocessedOre),
     ProcessedOre(ProcessedOre),
+    SwiftnessPotion(SwiftnessPotion),
+    StrengthPotion(StrengthPotion),
+    RegenerationPotion(RegenerationPotion),
+    ResistancePotion(ResistancePotion),
+    JumpPotion(JumpPotion),
+}
+
+/// Gives the player the [`ItemTrait::effect`] of the items it uses
+fn use_effect_items(
+    mut query: Query<(&mut EffectsController, &mut AnimationController), With<Player>>,
+    mut events: EventReader<UseItemEvent>,
+) {
+    if let Ok((mut effects, mut animation_controller)) = query.get_single_mut() {
+        for ev in events.read() {
+            if let Some((effect, seconds, level)) = ev.item.effect() {
+                animation_controller.play("Elixir");
+                effects.add_new(effect, seconds, level)
+            }
+        }
+    }
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::items::item::ItemTrait;
 
-#[derive(Clone, Deserialize, Serialize, Reflect, PartialEq, Eq, Default)]
+#[derive(Clone, Deserialize, Serialize, Reflect, PartialEq, Eq, Default, Debug)]
 pub struct Porkchop;
 
 impl ItemTrait for Porkchop {
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{effects::Effect, items::item::ItemTrait};
+
+#[derive(Clone, Deserialize, Serialize, Reflect, PartialEq, Default, Debug)]
+pub struct ResistancePotion {
+    pub seconds: f32,
+    pub level: u8,
+}
+
+impl Eq for ResistancePotion {}
+
+impl ItemTrait for ResistancePotion {
+    fn use_item(&self) -> bool {
+        true
+    }
+
+    fn effect(&self) -> Option<(Effect, f32, u8)> {
+        Some((Effect::Resistance, self.seconds, self.level))
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize, Serialize, Reflect, PartialEq, Eq, Default)]
+#[derive(Clone, Deserialize, Serialize, Reflect, PartialEq, Eq, Default, Debug)]
 pub struct UnprocessedOre(pub Ore);
 
 impl ItemTrait for UnprocessedOre {
@@ -1,6 +1,6 @@
 use crate::{items::stack::ItemStack, random::RandomWeightedTable};
 use bevy::prelude::*;
-use rand::{seq::IteratorRandom, thread_rng};
+use rand::{seq::IteratorRandom, Rng};
 use serde::Deserialize;
 use std::{fs, ops::Range, path::Path};
 
@@ -21,10 +21,10 @@ impl LootTable {
     }
 
     /// # Returns the amount of earned money and the looted items
-    pub fn get_random(&self) -> (u64, Vec<ItemStack>) {
+    pub fn get_random<R: Rng>(&self, rng: &mut R) -> (u64, Vec<ItemStack>) {
         (
-            self.money.clone().choose(&mut thread_rng()).unwrap_or(0),
-            self.items.get_random(),
+            self.money.clone().choose(rng).unwrap_or(0),
+            self.items.get_random(rng),
         )
     }
 }
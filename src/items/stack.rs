@@ -9,7 +9,7 @@ use super::{
     list::Item,
 };
 
-#[derive(Clone, Deserialize, Serialize, Reflect, Component)]
+#[derive(Clone, Deserialize, Serialize, Reflect, Component, PartialEq, Debug)]
 pub struct ItemStack {
     pub item: Item,
     pub count: u8,
@@ -44,9 +44,9 @@ pub enum LangIdentifier {
     English,
 }
 
-impl Into<String> for LangIdentifier {
-    fn into(self: LangIdentifier) -> String {
-        match self {
+impl From<LangIdentifier> for String {
+    fn from(value: LangIdentifier) -> Self {
+        match value {
             LangIdentifier::Français => "fr",
             LangIdentifier::English => "en",
         }
@@ -69,7 +69,7 @@ impl From<String> for LangIdentifier {
 
 impl From<Lang> for LangIdentifier {
     fn from(value: Lang) -> Self {
-        value.ident.into()
+        value.ident
     }
 }
 
@@ -0,0 +1,42 @@
+// Bevy systems take their queries and resources as arguments
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+pub mod animation;
+pub mod background;
+pub mod boss;
+pub mod chest;
+pub mod commands;
+pub mod damage;
+pub mod day_night;
+pub mod dungeon;
+pub mod effects;
+pub mod gui;
+pub mod interactable;
+pub mod items;
+pub mod lang;
+pub mod misc;
+pub mod mob;
+pub mod music;
+pub mod npc;
+pub mod ore;
+pub mod player;
+pub mod plugin;
+pub mod portal;
+pub mod projectile;
+pub mod random;
+pub mod save;
+pub mod state;
+pub mod stats;
+pub mod tiled;
+pub mod weather;
+pub mod world;
+
+use once_cell::sync::Lazy;
+use std::path::PathBuf;
+
+pub const GAME_NAME: &str = "Terradventure";
+
+static CONFIG_DIR: Lazy<PathBuf> = Lazy::new(|| {
+    let dir = dirs::config_dir().unwrap();
+    dir.join(GAME_NAME)
+});
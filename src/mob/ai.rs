@@ -0,0 +1,461 @@
+use super::MobType;
+use crate::{
+    animation::AnimationController, gui::misc::ease_out_quad, stats::Stats, world::BLOCK_SIZE,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::random;
+use std::time::Duration;
+
+/// What a [`MobAi`] knows about its mob and its surroundings for one frame
+pub struct MobAiContext<'a> {
+    pub transform: &'a Transform,
+    pub controller: &'a mut KinematicCharacterController,
+    pub animation_controller: &'a mut AnimationController,
+    pub sprite: &'a mut TextureAtlasSprite,
+    pub stats: &'a Stats,
+    pub time: &'a Time,
+    /// Used to look at the terrain around the mob
+    pub rapier_context: &'a RapierContext,
+    /// Half the size of the collider of the mob
+    pub half_size: Vec2,
+    /// Whether the mob stands on the ground
+    pub grounded: bool,
+    /// Running while the mob goes up, paused otherwise
+    pub jump_timer: &'a mut Timer,
+    /// Position of the player, [`None`] if there is no player in the world
+    pub player: Option<Vec2>,
+    /// Whether the mob was hit since the last frame
+    pub hurt: bool,
+    /// Where the mob shot its projectile this frame, relative to the mob
+    pub shot: Option<Vec2>,
+}
+
+impl MobAiContext<'_> {
+    /// Highest step a mob jumps onto instead of turning around
+    pub const MAX_STEP_HEIGHT: f32 = 2. * BLOCK_SIZE;
+    /// Deepest drop a mob walks down instead of turning around
+    pub const MAX_DROP: f32 = 3. * BLOCK_SIZE;
+    /// How far in front of the mob the terrain is checked
+    const LOOKAHEAD: f32 = BLOCK_SIZE / 2.;
+
+    /// Casts a ray against the terrain only, mobs and the player don't stop the ray
+    fn cast_terrain(&self, origin: Vec2, direction: Vec2, max_distance: f32) -> bool {
+        self.rapier_context
+            .cast_ray(
+                origin,
+                direction,
+                max_distance,
+                true,
+                QueryFilter::only_fixed().exclude_sensors(),
+            )
+            .is_some()
+    }
+
+    fn feet(&self) -> Vec2 {
+        self.transform.translation.xy() - Vec2::new(0., self.half_size.y)
+    }
+
+    /// # Returns
+    /// Whether walking towards `direction` would drop the mob further than [`Self::MAX_DROP`]
+    pub fn ledge_ahead(&self, direction: f32) -> bool {
+        let origin = self.transform.translation.xy()
+            + Vec2::new(
+                direction.signum() * (self.half_size.x + Self::LOOKAHEAD),
+                0.,
+            );
+
+        !self.cast_terrain(origin, Vec2::NEG_Y, self.half_size.y + Self::MAX_DROP)
+    }
+
+    /// # Returns
+    /// Whether something blocks the feet of the mob when walking towards `direction`
+    fn obstacle_at(&self, direction: f32, height: f32) -> bool {
+        self.cast_terrain(
+            self.feet() + Vec2::new(0., height),
+            Vec2::new(direction.signum(), 0.),
+            self.half_size.x + Self::LOOKAHEAD,
+        )
+    }
+
+    /// # Returns
+    /// Whether a step low enough to jump onto is in front of the mob
+    pub fn step_ahead(&self, direction: f32) -> bool {
+        self.obstacle_at(direction, 2.) && !self.obstacle_at(direction, Self::MAX_STEP_HEIGHT + 1.)
+    }
+
+    /// # Returns
+    /// Whether a wall too high to jump over is in front of the mob
+    pub fn wall_ahead(&self, direction: f32) -> bool {
+        self.obstacle_at(direction, Self::MAX_STEP_HEIGHT + 1.)
+    }
+
+    /// # Returns
+    /// Whether the mob can't go further towards `direction`, because of a wall or a ledge
+    pub fn blocked(&self, direction: f32) -> bool {
+        self.wall_ahead(direction) || self.ledge_ahead(direction)
+    }
+
+    pub fn jump(&mut self) {
+        self.jump_timer.reset();
+        self.jump_timer.unpause();
+    }
+
+    /// # Returns
+    /// The offset from the mob to the player
+    pub fn to_player(&self) -> Option<Vec2> {
+        self.player
+            .map(|player| player - self.transform.translation.xy())
+    }
+
+    /// # Returns
+    /// The distance walked this frame at the speed of the mob
+    pub fn step(&self, speed_multiplier: f32) -> f32 {
+        self.stats.speed * speed_multiplier * self.time.delta_seconds()
+    }
+
+    /// Moves the mob horizontally this frame, jumping onto the steps in its way
+    pub fn walk(&mut self, distance: f32) {
+        if distance != 0. {
+            self.sprite.flip_x = distance < 0.;
+            self.play("Walk");
+
+            if self.grounded && self.jump_timer.paused() && self.step_ahead(distance) {
+                self.jump();
+            }
+        } else if self.animation_controller.current_animation == Some("Walk".to_owned()) {
+            self.animation_controller.stop();
+        }
+
+        if self.jump_timer.finished() {
+            self.jump_timer.pause();
+        }
+
+        let mut vertical = -1.;
+        if !self.jump_timer.paused() {
+            self.jump_timer.tick(self.time.delta());
+
+            // Same impulsion as the player
+            vertical = 2.5 * ease_out_quad(self.jump_timer.percent());
+        }
+
+        self.controller.translation = Some(Vec2::new(
+            distance,
+            vertical * self.stats.mass * self.time.delta_seconds(),
+        ));
+    }
+
+    /// Fires the projectile of the mob at a target, relative to the mob
+    pub fn shoot(&mut self, target: Vec2) {
+        self.shot = Some(target);
+    }
+
+    /// Plays an animation if the mob has it, without restarting it
+    pub fn play(&mut self, name: &str) {
+        if self.animation_controller.animations.get(name).is_some()
+            && self.animation_controller.current_animation.as_deref() != Some(name)
+        {
+            self.animation_controller.play(name);
+        }
+    }
+}
+
+pub trait MobAi: Sync + Send {
+    /// Moves the mob for this frame
+    /// # Returns
+    /// The damage dealt to the player
+    fn update(&mut self, context: &mut MobAiContext) -> Option<f32>;
+}
+
+impl From<MobType> for Box<dyn MobAi> {
+    fn from(value: MobType) -> Self {
+        match value {
+            MobType::Passive => Box::<PassiveMobAi>::default(),
+            MobType::Neutral => Box::<NeutralMobAi>::default(),
+            MobType::Aggressive => Box::<AggressiveMobAi>::default(),
+            MobType::Ranged => Box::<RangedMobAi>::default(),
+        }
+    }
+}
+
+/// Walks left and right at random, what mobs do when nothing draws their attention
+#[derive(Default)]
+pub struct Wander {
+    /// time until next wander (if wandering => timeout)
+    pub timer: Timer,
+
+    /// x coord of the destination, None if not wandering
+    pub destination: Option<f32>,
+}
+
+impl Wander {
+    const MAX_DISTANCE: f32 = 15.;
+    const TIMEOUT: f32 = 10.;
+
+    pub fn update(&mut self, context: &mut MobAiContext) {
+        let mut movement = 0.;
+        self.timer.tick(context.time.delta());
+
+        if let Some(destination) = self.destination {
+            let destination_dist = destination - context.transform.translation.x;
+            let destination_reached = destination_dist == 0.0;
+            let timed_out = self.timer.finished();
+
+            if destination_reached || timed_out {
+                self.rest();
+            } else if context.blocked(destination_dist) {
+                // Turn around at ledges and walls
+                self.destination = Some(context.transform.translation.x - destination_dist);
+            } else {
+                let step = destination_dist.signum() * context.step(1.);
+
+                movement = if destination_dist.abs() > step.abs() {
+                    step
+                } else {
+                    destination_dist
+                };
+            }
+        } else if self.timer.finished() {
+            let direction = (random::<f32>() - 0.5) * BLOCK_SIZE * 2. * Self::MAX_DISTANCE;
+
+            self.destination = Some(context.transform.translation.x + direction);
+
+            self.timer
+                .set_duration(Duration::from_secs_f32(Self::TIMEOUT));
+            self.timer.reset();
+        }
+
+        context.walk(movement);
+    }
+
+    /// Stops walking, the next walk starts after a random pause
+    pub fn rest(&mut self) {
+        self.timer
+            .set_duration(Duration::from_secs_f32(10. * random::<f32>() + 5.));
+        self.timer.reset();
+        self.destination = None;
+    }
+}
+
+/// Runs to the player and hits it once close enough
+pub struct MeleeAttack {
+    pub cooldown: Timer,
+}
+
+impl Default for MeleeAttack {
+    fn default() -> Self {
+        Self {
+            cooldown: Timer::from_seconds(Self::COOLDOWN, TimerMode::Once),
+        }
+    }
+}
+
+impl MeleeAttack {
+    /// Before the strength of the mob scales it
+    pub const DAMAGE: f32 = 2.;
+    const COOLDOWN: f32 = 1.2;
+    const CHASE_SPEED_MULTIPLIER: f32 = 1.3;
+    /// How far the player can be to be hit
+    const REACH: Vec2 = Vec2::new(2. * BLOCK_SIZE, 3. * BLOCK_SIZE);
+
+    /// # Returns
+    /// The base damage dealt to the player
+    pub fn update(&mut self, context: &mut MobAiContext, to_player: Vec2) -> Option<f32> {
+        self.cooldown.tick(context.time.delta());
+
+        if to_player.abs().cmpgt(Self::REACH).any() {
+            // Jumps down to a player below but doesn't fall off the platform it's standing on otherwise
+            let stuck = context.wall_ahead(to_player.x)
+                || (context.ledge_ahead(to_player.x) && to_player.y > -Self::REACH.y);
+
+            let step = context.step(Self::CHASE_SPEED_MULTIPLIER);
+            if stuck {
+                context.walk(0.);
+                context.sprite.flip_x = to_player.x < 0.;
+            } else {
+                context.walk(to_player.x.signum() * step.min(to_player.x.abs()));
+            }
+            return None;
+        }
+
+        context.walk(0.);
+        context.sprite.flip_x = to_player.x < 0.;
+
+        if !self.cooldown.finished() {
+            return None;
+        }
+
+        self.cooldown.reset();
+        context.play("Attack");
+        Some(Self::DAMAGE)
+    }
+}
+
+/// Wanders around and runs away from the player for a while when hurt
+#[derive(Default)]
+pub struct PassiveMobAi {
+    pub wander: Wander,
+    /// Time left running away, [`None`] if the mob isn't scared
+    pub flee_timer: Option<Timer>,
+}
+
+impl PassiveMobAi {
+    const FLEE_DURATION: f32 = 4.;
+    const FLEE_SPEED_MULTIPLIER: f32 = 1.6;
+}
+
+impl MobAi for PassiveMobAi {
+    fn update(&mut self, context: &mut MobAiContext) -> Option<f32> {
+        if context.hurt {
+            self.flee_timer = Some(Timer::from_seconds(Self::FLEE_DURATION, TimerMode::Once));
+        }
+
+        if let Some(flee_timer) = &mut self.flee_timer {
+            flee_timer.tick(context.time.delta());
+            if flee_timer.finished() {
+                self.flee_timer = None;
+                self.wander.rest();
+            }
+        }
+
+        match (&self.flee_timer, context.to_player()) {
+            (Some(_), Some(to_player)) => {
+                let direction = if to_player.x > 0. { -1. } else { 1. };
+
+                // Cornered, better stay here than jump off a cliff
+                if context.blocked(direction) {
+                    context.walk(0.);
+                } else {
+                    context.walk(direction * context.step(Self::FLEE_SPEED_MULTIPLIER));
+                }
+            }
+            _ => self.wander.update(context),
+        }
+
+        None
+    }
+}
+
+/// Wanders around until hurt, then fights back until the player runs away
+#[derive(Default)]
+pub struct NeutralMobAi {
+    pub wander: Wander,
+    pub attack: MeleeAttack,
+    /// Time left before calming down, [`None`] if the mob isn't angry
+    pub anger_timer: Option<Timer>,
+}
+
+impl NeutralMobAi {
+    const ANGER_DURATION: f32 = 15.;
+    /// The mob calms down when the player gets this far
+    const GIVE_UP_DISTANCE: f32 = 20. * BLOCK_SIZE;
+}
+
+impl MobAi for NeutralMobAi {
+    fn update(&mut self, context: &mut MobAiContext) -> Option<f32> {
+        if context.hurt {
+            self.anger_timer = Some(Timer::from_seconds(Self::ANGER_DURATION, TimerMode::Once));
+        }
+
+        let target = context
+            .to_player()
+            .filter(|to_player| to_player.length() <= Self::GIVE_UP_DISTANCE);
+
+        if let Some(anger_timer) = &mut self.anger_timer {
+            anger_timer.tick(context.time.delta());
+            if anger_timer.finished() || target.is_none() {
+                self.anger_timer = None;
+                self.wander.rest();
+            }
+        }
+
+        match (&self.anger_timer, target) {
+            (Some(_), Some(to_player)) => self.attack.update(context, to_player),
+            _ => {
+                self.wander.update(context);
+                None
+            }
+        }
+    }
+}
+
+/// Wanders around and attacks the player as soon as it comes close
+#[derive(Default)]
+pub struct AggressiveMobAi {
+    pub wander: Wander,
+    pub attack: MeleeAttack,
+}
+
+impl AggressiveMobAi {
+    const DETECTION_RADIUS: f32 = 10. * BLOCK_SIZE;
+}
+
+impl MobAi for AggressiveMobAi {
+    fn update(&mut self, context: &mut MobAiContext) -> Option<f32> {
+        let target = context
+            .to_player()
+            .filter(|to_player| to_player.length() <= Self::DETECTION_RADIUS);
+
+        match target {
+            Some(to_player) => self.attack.update(context, to_player),
+            None => {
+                self.wander.update(context);
+                None
+            }
+        }
+    }
+}
+
+/// Keeps its distance from the player and shoots at it
+pub struct RangedMobAi {
+    pub wander: Wander,
+    pub cooldown: Timer,
+}
+
+impl Default for RangedMobAi {
+    fn default() -> Self {
+        Self {
+            wander: Wander::default(),
+            cooldown: Timer::from_seconds(Self::COOLDOWN, TimerMode::Once),
+        }
+    }
+}
+
+impl RangedMobAi {
+    const COOLDOWN: f32 = 2.;
+    const DETECTION_RADIUS: f32 = 14. * BLOCK_SIZE;
+    /// The mob backs away from a closer player
+    const MIN_DISTANCE: f32 = 5. * BLOCK_SIZE;
+}
+
+impl MobAi for RangedMobAi {
+    fn update(&mut self, context: &mut MobAiContext) -> Option<f32> {
+        self.cooldown.tick(context.time.delta());
+
+        let target = context
+            .to_player()
+            .filter(|to_player| to_player.length() <= Self::DETECTION_RADIUS);
+
+        let Some(to_player) = target else {
+            self.wander.update(context);
+            return None;
+        };
+
+        let away = -to_player.x.signum();
+        if to_player.length() < Self::MIN_DISTANCE && !context.blocked(away) {
+            context.walk(away * context.step(1.));
+        } else {
+            context.walk(0.);
+        }
+        // Always faces the player, even while backing away
+        context.sprite.flip_x = to_player.x < 0.;
+
+        if self.cooldown.finished() {
+            self.cooldown.reset();
+            context.play("Attack");
+            context.shoot(to_player);
+        }
+
+        None
+    }
+}
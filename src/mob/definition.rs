@@ -0,0 +1,518 @@
+use super::{ai::MeleeAttack, list::MobObject, MobLootTable, MobTrait, MobType};
+use crate::{
+    animation::{
+        AnimatedSpriteBundle, Animation, AnimationController, AnimationDirection, AnimationMode,
+    },
+    day_night::TimeOfDay,
+    projectile::{Faction, Projectile, ProjectileBundle},
+    random::RandomWeightedRate,
+    stats::Stats,
+    world::{MobSpawnRate, BLOCK_SIZE},
+};
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadState, LoadedFolder},
+    prelude::*,
+    sprite::Anchor,
+    utils::{hashbrown::HashMap, BoxedFuture},
+};
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{any::TypeId, ops::RangeInclusive, sync::Arc, time::Duration};
+use thiserror::Error;
+
+pub struct MobDefinitionPlugin;
+impl Plugin for MobDefinitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<MobDefinition>()
+            .init_resource::<MobDefinitions>()
+            .register_asset_loader(MobDefinitionLoader)
+            .add_systems(Startup, load_mob_definitions)
+            .add_systems(Update, register_mob_definitions);
+    }
+}
+
+/// Every loaded [`MobDefinition`] by name, so saves and Tiled maps can find them from their name only
+#[derive(Resource, Default)]
+pub struct MobDefinitions {
+    definitions: HashMap<String, Arc<MobDefinition>>,
+    /// Name of each definition asset, to forget it once modified or removed
+    names: HashMap<AssetId<MobDefinition>, String>,
+}
+
+/// A mob described by a `.mob` file of `assets/mobs/`, spawned through [`DefinedMob`]
+#[derive(Asset, TypePath, Deserialize, Clone)]
+pub struct MobDefinition {
+    /// Used by Tiled maps and saves, the textures are in `textures/mobs/<name>/`
+    pub name: String,
+    pub ai: MobType,
+    #[serde(default)]
+    pub stats: Stats,
+    pub collider: MobCollider,
+    pub sprite: MobSprite,
+    /// Sprite sheets by animation name, "Idle" and "Walk" are used by every AI
+    pub animations: HashMap<String, MobAnimation>,
+    /// Loot table of `loot_tables/mobs/`, the name of the mob if missing
+    pub loot_table: Option<String>,
+    /// Experience given when killed, half the max health if missing
+    pub xp: Option<u64>,
+    /// Shot by the mob if its AI is ranged
+    pub projectile: Option<MobProjectile>,
+    /// Biomes where the mob spawns naturally, added to their own spawn rates
+    #[serde(default)]
+    pub spawns: Vec<MobBiomeSpawn>,
+}
+
+/// Collider shape, in pixels
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum MobCollider {
+    Ball { radius: f32 },
+    CapsuleX { half_length: f32, radius: f32 },
+    CapsuleY { half_length: f32, radius: f32 },
+    Cuboid { half_width: f32, half_height: f32 },
+}
+
+impl From<&MobCollider> for Collider {
+    fn from(val: &MobCollider) -> Collider {
+        match *val {
+            MobCollider::Ball { radius } => Collider::ball(radius),
+            MobCollider::CapsuleX {
+                half_length,
+                radius,
+            } => Collider::capsule_x(half_length, radius),
+            MobCollider::CapsuleY {
+                half_length,
+                radius,
+            } => Collider::capsule_y(half_length, radius),
+            MobCollider::Cuboid {
+                half_width,
+                half_height,
+            } => Collider::cuboid(half_width, half_height),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct MobSprite {
+    /// Width of a frame of the sprite sheets
+    pub tile_size: u32,
+    pub size: Option<Vec2>,
+    pub anchor: Option<Vec2>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct MobAnimation {
+    /// Seconds for the whole animation
+    pub duration: f32,
+    pub mode: AnimationMode,
+    #[serde(default)]
+    pub direction: AnimationDirection,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct MobProjectile {
+    /// Sprite sheet in the textures of the mob, its first frame is shown while flying and the others once it hits
+    pub animation: String,
+    pub tile_size: u32,
+    /// Seconds for the hit animation
+    pub duration: f32,
+    pub size: Option<Vec2>,
+    /// Radius of the collider, in pixels
+    pub radius: f32,
+    /// Pixels per second
+    pub speed: f32,
+    /// Pixels per second squared, the projectile flies straight if missing
+    #[serde(default)]
+    pub gravity: f32,
+    #[serde(default)]
+    pub pierce: u32,
+    /// Blocks flown before disappearing
+    pub range: f32,
+    /// Before the strength of the mob scales it, the damage of a melee attack if missing
+    pub damage: Option<f32>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct MobBiomeSpawn {
+    /// [`WorldTrait::name`](crate::world::WorldTrait::name) of the biome
+    pub biome: String,
+    pub weight: u32,
+    pub group: RangeInclusive<u32>,
+    /// Parts of the day the mob is out, always if empty
+    #[serde(default)]
+    pub times: Vec<TimeOfDay>,
+}
+
+impl MobDefinitions {
+    pub fn get(&self, name: &str) -> Option<Arc<MobDefinition>> {
+        self.definitions.get(name).cloned()
+    }
+
+    /// # Returns
+    /// Every defined mob, ready to spawn
+    pub fn mobs(&self) -> impl Iterator<Item = DefinedMob> + '_ {
+        self.definitions.values().map(|definition| DefinedMob {
+            name: definition.name.clone(),
+            definition: Some(definition.clone()),
+        })
+    }
+
+    /// # Returns
+    /// The mob with its definition, if it's a [`DefinedMob`] loaded from a save or built from its name only
+    pub fn resolve(&self, mob: MobObject) -> MobObject {
+        match mob {
+            MobObject::Defined(DefinedMob {
+                name,
+                definition: None,
+            }) => DefinedMob {
+                definition: self.get(&name),
+                name,
+            }
+            .into(),
+            mob => mob,
+        }
+    }
+
+    /// # Returns
+    /// The spawn rates of the defined mobs living in a biome at this time of day
+    pub fn biome_spawn_rates(
+        &self,
+        biome: &str,
+        time: TimeOfDay,
+    ) -> Vec<RandomWeightedRate<MobSpawnRate>> {
+        self.definitions
+            .values()
+            .flat_map(|definition| {
+                definition
+                    .spawns
+                    .iter()
+                    .filter(|spawn| {
+                        spawn.biome == biome
+                            && (spawn.times.is_empty() || spawn.times.contains(&time))
+                    })
+                    .map(|spawn| RandomWeightedRate {
+                        data: MobSpawnRate {
+                            mob: DefinedMob {
+                                name: definition.name.clone(),
+                                definition: Some(definition.clone()),
+                            }
+                            .into(),
+                            group: spawn.group.clone(),
+                        },
+                        weight: spawn.weight,
+                    })
+            })
+            .collect()
+    }
+
+    fn insert(&mut self, id: AssetId<MobDefinition>, definition: &MobDefinition) {
+        self.remove(id);
+        self.names.insert(id, definition.name.clone());
+        self.definitions
+            .insert(definition.name.clone(), Arc::new(definition.clone()));
+    }
+
+    fn remove(&mut self, id: AssetId<MobDefinition>) {
+        if let Some(name) = self.names.remove(&id) {
+            self.definitions.remove(&name);
+        }
+    }
+}
+
+impl MobDefinition {
+    /// # Returns
+    /// Why the mob could not be spawned, if it can't
+    fn validate(&self) -> Result<(), &'static str> {
+        if self.sprite.tile_size == 0 {
+            return Err("the sprite tile_size must be positive");
+        }
+        if self
+            .animations
+            .values()
+            .any(|animation| !(animation.duration.is_finite() && animation.duration > 0.))
+        {
+            return Err("animation durations must be positive");
+        }
+        if self
+            .spawns
+            .iter()
+            .any(|spawn| spawn.group.is_empty() || *spawn.group.end() == 0)
+        {
+            return Err("spawn groups must hold at least one mob");
+        }
+        self.projectile
+            .as_ref()
+            .map_or(Ok(()), MobProjectile::validate)
+    }
+}
+
+impl MobProjectile {
+    /// # Returns
+    /// Why the projectile could not be shot, if it can't
+    fn validate(&self) -> Result<(), &'static str> {
+        if self.tile_size == 0 {
+            Err("the projectile tile_size must be positive")
+        } else if !(self.duration.is_finite() && self.duration > 0.) {
+            Err("the projectile duration must be positive")
+        } else if !(self.speed.is_finite() && self.speed > 0.) {
+            Err("the projectile speed must be positive")
+        } else if !(self.range.is_finite() && self.range >= 0.) {
+            Err("the projectile range can't be negative")
+        } else if !(self.radius.is_finite() && self.radius > 0.) {
+            Err("the projectile radius must be positive")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MobDefinitionLoader;
+
+#[derive(Debug, Error)]
+pub enum MobDefinitionLoaderError {
+    #[error("Could not load mob definition: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid mob definition: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid mob definition {name}: {reason}")]
+    Invalid { name: String, reason: &'static str },
+}
+
+impl AssetLoader for MobDefinitionLoader {
+    type Asset = MobDefinition;
+    type Settings = ();
+    type Error = MobDefinitionLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let definition: MobDefinition = serde_json::from_slice(&bytes)?;
+            definition
+                .validate()
+                .map_err(|reason| MobDefinitionLoaderError::Invalid {
+                    name: definition.name.clone(),
+                    reason,
+                })?;
+            Ok(definition)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["mob"];
+        EXTENSIONS
+    }
+}
+
+/// Keeps the definitions loaded
+#[derive(Resource)]
+pub struct MobDefinitionFolder(Handle<LoadedFolder>);
+
+fn load_mob_definitions(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(MobDefinitionFolder(asset_server.load_folder("mobs")));
+}
+
+fn register_mob_definitions(
+    mut events: EventReader<AssetEvent<MobDefinition>>,
+    assets: Res<Assets<MobDefinition>>,
+    mut definitions: ResMut<MobDefinitions>,
+) {
+    for event in events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                let Some(definition) = assets.get(*id) else {
+                    continue;
+                };
+                info!("Loaded mob {}", definition.name);
+                definitions.insert(*id, definition);
+            }
+            AssetEvent::Removed { id } => definitions.remove(*id),
+            _ => {}
+        }
+    }
+}
+
+/// Whether every `.mob` file was registered or failed to load, worlds can't be entered before
+pub fn mob_definitions_loaded(
+    folder: Res<MobDefinitionFolder>,
+    folders: Res<Assets<LoadedFolder>>,
+    definitions: Res<MobDefinitions>,
+    asset_server: Res<AssetServer>,
+) -> bool {
+    if asset_server.get_load_state(&folder.0) == Some(LoadState::Failed) {
+        return true;
+    }
+    let Some(folder) = folders.get(&folder.0) else {
+        return false;
+    };
+
+    folder.handles.iter().all(|handle| {
+        if handle.id().type_id() != TypeId::of::<MobDefinition>() {
+            return true;
+        }
+        let id = handle.id().typed::<MobDefinition>();
+        definitions.names.contains_key(&id)
+            || asset_server.get_load_state(id) == Some(LoadState::Failed)
+    })
+}
+
+/// Mob spawned from its [`MobDefinition`], only the name is saved
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct DefinedMob {
+    pub name: String,
+    /// Found with [`MobDefinitions::resolve`] for the mobs built from their name only
+    #[serde(skip)]
+    definition: Option<Arc<MobDefinition>>,
+}
+
+impl DefinedMob {
+    /// The definition is missing until [`MobDefinitions::resolve`] finds it
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            definition: None,
+        }
+    }
+
+    fn definition(&self) -> Option<&MobDefinition> {
+        let definition = self.definition.as_deref();
+        if definition.is_none() {
+            error!("Mob {} has no definition", self.name);
+        }
+        definition
+    }
+}
+
+impl MobTrait for DefinedMob {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn animations(&self, asset_server: &Res<AssetServer>) -> HashMap<String, Animation> {
+        let Some(definition) = self.definition() else {
+            return HashMap::new();
+        };
+
+        definition
+            .animations
+            .iter()
+            .map(|(name, animation)| {
+                (
+                    name.clone(),
+                    Animation::new(
+                        self.texture(name),
+                        asset_server,
+                        Duration::from_secs_f32(animation.duration),
+                        definition.sprite.tile_size,
+                        animation.mode,
+                        animation.direction,
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn typ(&self) -> MobType {
+        self.definition()
+            .map(|definition| definition.ai.clone())
+            .unwrap_or(MobType::Passive)
+    }
+
+    fn death_loot_table(&self) -> MobLootTable {
+        MobLootTable(
+            self.definition()
+                .and_then(|definition| definition.loot_table.clone())
+                .unwrap_or_else(|| self.name.clone()),
+        )
+    }
+
+    fn sprite_custom_size_and_anchor(&self) -> (Option<Vec2>, Option<Anchor>) {
+        self.definition()
+            .map(|definition| {
+                (
+                    definition.sprite.size,
+                    definition.sprite.anchor.map(Anchor::Custom),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    fn default_stats(&self) -> Stats {
+        self.definition()
+            .map(|definition| definition.stats.clone())
+            .unwrap_or_default()
+    }
+
+    fn collider(&self) -> Collider {
+        self.definition()
+            .map(|definition| (&definition.collider).into())
+            .unwrap_or_else(|| Collider::ball(BLOCK_SIZE / 2.))
+    }
+
+    fn xp(&self) -> u64 {
+        self.definition()
+            .and_then(|definition| definition.xp)
+            .unwrap_or_else(|| (self.default_stats().max_health / 2.).ceil() as u64)
+    }
+
+    fn projectile(
+        &self,
+        asset_server: &Res<AssetServer>,
+        pos: Vec2,
+        target: Vec2,
+    ) -> Option<ProjectileBundle> {
+        let definition = self.definition()?;
+        let projectile = definition.projectile.as_ref()?;
+
+        let mut animations = HashMap::new();
+        animations.insert(
+            projectile.animation.clone(),
+            Animation::new(
+                self.texture(&projectile.animation),
+                asset_server,
+                Duration::from_secs_f32(projectile.duration),
+                projectile.tile_size,
+                AnimationMode::Custom,
+                AnimationDirection::Forwards,
+            ),
+        );
+        let mut animation_controller = AnimationController::new(animations);
+        animation_controller.play(&projectile.animation);
+
+        let velocity = Projectile::aim(target, projectile.speed, projectile.gravity);
+        let flip_x = velocity.x < 0.;
+        let heading = if flip_x { -velocity } else { velocity };
+
+        Some(ProjectileBundle {
+            projectile: Projectile::new(
+                velocity,
+                projectile.damage.unwrap_or(MeleeAttack::DAMAGE),
+                Faction::Mob,
+            )
+            .with_gravity(projectile.gravity)
+            .with_pierce(projectile.pierce)
+            .with_lifetime(projectile.range * BLOCK_SIZE / projectile.speed),
+            collider: Collider::ball(projectile.radius),
+            sensor: Sensor,
+            sprite: AnimatedSpriteBundle {
+                animation_controller,
+                sprite: SpriteSheetBundle {
+                    sprite: TextureAtlasSprite {
+                        flip_x,
+                        custom_size: projectile.size,
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(pos.extend(15.))
+                        .with_rotation(Quat::from_rotation_z(heading.y.atan2(heading.x))),
+                    ..Default::default()
+                },
+            },
+        })
+    }
+}
@@ -2,6 +2,11 @@ use bevy::ecs::component::Component;
 use enum_dispatch::enum_dispatch;
 use serde::{Deserialize, Serialize};
 
+use super::{
+    definition::{DefinedMob, MobDefinitions},
+    MobTrait,
+};
+
 use self::pig::Pig;
 use self::rabbit::Rabbit;
 
@@ -13,4 +18,22 @@ pub mod rabbit;
 pub enum MobObject {
     Rabbit(Rabbit),
     Pig(Pig),
+    Defined(DefinedMob),
+}
+
+impl MobObject {
+    /// # Returns
+    /// Every mob of the game, with the loaded [`MobDefinitions`]
+    pub fn all(definitions: &MobDefinitions) -> Vec<Self> {
+        let mut mobs: Vec<Self> = vec![Rabbit.into(), Pig.into()];
+        mobs.extend(definitions.mobs().map(Self::from));
+        mobs
+    }
+
+    /// Parses the [`MobTrait::name`] of a mob
+    pub fn from_name(name: &str, definitions: &MobDefinitions) -> Option<Self> {
+        Self::all(definitions)
+            .into_iter()
+            .find(|mob| mob.name() == name)
+    }
 }
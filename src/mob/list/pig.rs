@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 pub struct Pig;
 
 impl MobTrait for Pig {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "pig"
     }
     fn animations(&self, asset_server: &Res<AssetServer>) -> HashMap<String, Animation> {
@@ -23,10 +23,10 @@ impl MobTrait for Pig {
         ])
     }
     fn default_stats(&self) -> Stats {
-        Stats::default().with_health(15.0)
+        Stats::default().with_health(15.0).with_strength(2.0)
     }
     fn typ(&self) -> MobType {
-        MobType::Passive
+        MobType::Neutral
     }
 
     fn collider(&self) -> Collider {
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 pub struct Rabbit;
 
 impl MobTrait for Rabbit {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "rabbit"
     }
     fn animations(&self, asset_server: &Res<AssetServer>) -> HashMap<String, Animation> {
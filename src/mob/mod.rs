@@ -1,57 +1,145 @@
+pub mod ai;
+pub mod definition;
 pub mod list;
+pub mod spawn;
 
 use crate::{
     animation::{AnimatedSpriteBundle, Animation, AnimationController},
+    damage::DamageEvent,
+    effects::EffectsController,
     items::{loot_table::LootTable, stack::ItemStack},
-    save::SaveData,
+    player::Player,
+    portal::is_travelling,
+    projectile::ProjectileBundle,
     state::AppState,
     stats::Stats,
-    world::{is_loading, BLOCK_SIZE},
+    weather::WorldWeather,
+    world::is_loading,
 };
 use bevy::{prelude::*, sprite::Anchor, utils::hashbrown::HashMap};
 use bevy_rapier2d::prelude::*;
 use enum_dispatch::enum_dispatch;
-use rand::random;
-use std::{
-    path::{Path, PathBuf},
-    time::Duration,
+use rand::Rng;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use self::{
+    ai::{MobAi, MobAiContext},
+    definition::{DefinedMob, MobDefinitionPlugin},
+    list::{pig::Pig, rabbit::Rabbit, MobObject},
+    spawn::{count_mobs, despawn_far_mobs, populate_spawn_zones},
 };
 
-use self::list::{pig::Pig, rabbit::Rabbit, MobObject};
-
 pub struct MobPlugin;
 impl Plugin for MobPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.add_plugins(MobDefinitionPlugin).add_systems(
             Update,
-            (update_ai.run_if(not(is_loading)), mob_hit).run_if(in_state(AppState::InGame)),
-        )
-        .add_systems(OnEnter(AppState::InGame), load_mobs);
+            (
+                update_ai.run_if(not(is_loading)),
+                (count_mobs, populate_spawn_zones, despawn_far_mobs)
+                    .chain()
+                    .run_if(not(is_loading).and_then(not(is_travelling))),
+                mob_hit,
+            )
+                .run_if(in_state(AppState::InGame)),
+        );
     }
 }
 
 fn update_ai(
-    mut query: Query<(
-        &mut Mob,
-        &mut KinematicCharacterController,
-        &Transform,
-        &mut Stats,
-        &mut TextureAtlasSprite,
-        &mut AnimationController,
-    )>,
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &MobObject,
+            &mut Mob,
+            &mut KinematicCharacterController,
+            &Transform,
+            &Stats,
+            &EffectsController,
+            &mut TextureAtlasSprite,
+            &mut AnimationController,
+            &Collider,
+            Option<&KinematicCharacterControllerOutput>,
+        ),
+        Without<Player>,
+    >,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    mut damage_event: EventWriter<DamageEvent>,
+    weather_query: Query<&WorldWeather>,
+    rapier_context: Res<RapierContext>,
+    asset_server: Res<AssetServer>,
     time: Res<Time>,
 ) {
-    for (mut mob, mut controller, transform, mut stats, mut sprite, mut animation_controller) in
-        query.iter_mut()
+    let speed_multiplier = weather_query
+        .get_single()
+        .map(|weather| weather.current.speed_multiplier())
+        .unwrap_or(1.);
+
+    let player = player_query.get_single().ok();
+    let player_pos = player.map(|(_, transform)| transform.translation.xy());
+
+    for (
+        entity,
+        object,
+        mut mob,
+        mut controller,
+        transform,
+        stats,
+        effects,
+        mut sprite,
+        mut animation_controller,
+        collider,
+        output,
+    ) in query.iter_mut()
     {
-        mob.ai.update(
+        let half_size = collider.raw.compute_local_aabb().half_extents();
+        let mob = &mut *mob;
+        let mut context = MobAiContext {
             transform,
-            &mut controller,
-            &mut animation_controller,
-            &mut sprite,
-            &mut stats,
-            &time,
-        );
+            controller: &mut controller,
+            animation_controller: &mut animation_controller,
+            sprite: &mut sprite,
+            stats,
+            time: &time,
+            rapier_context: &rapier_context,
+            half_size: Vec2::new(half_size.x, half_size.y),
+            grounded: output.is_some_and(|output| output.grounded),
+            jump_timer: &mut mob.jump_timer,
+            player: player_pos,
+            hurt: std::mem::take(&mut mob.hurt),
+            shot: None,
+        };
+        let damage = mob.ai.update(&mut context);
+        let shot = context.shot;
+
+        if let (Some(damage), Some((player, _))) = (damage, player) {
+            damage_event.send(DamageEvent::new(Some(entity), player, damage));
+        }
+
+        if let Some(target) = shot {
+            let pos = transform.translation.xy();
+            match object.projectile(&asset_server, pos, target) {
+                Some(mut projectile) => {
+                    projectile.projectile.owner = Some(entity);
+                    commands.spawn(projectile);
+                }
+                None => warn!("Mob {} shot without a projectile", object.name()),
+            }
+        }
+
+        // The weather and effects slow walking only, not falling
+        if let Some(translation) = &mut controller.translation {
+            translation.x *= speed_multiplier * effects.speed_multiplier();
+
+            // Effects also lift the mob or make it jump higher
+            if let Some(lift) = effects.levitation() {
+                translation.y = lift * stats.mass * time.delta_seconds();
+            } else if translation.y > 0. {
+                translation.y *= effects.jump_multiplier();
+            }
+        }
     }
 }
 
@@ -61,30 +149,38 @@ pub struct Mob {
     pub typ: MobType,
     pub death_loot_table: MobLootTable,
     pub ai: Box<dyn MobAi>,
+    /// Whether the mob was hit since its last AI update
+    hurt: bool,
+    pub jump_timer: Timer,
 }
 
 impl Mob {
     pub fn new(typ: MobType, death_loot_table: MobLootTable) -> Self {
         let mut hit_timer = Timer::from_seconds(0.3, TimerMode::Once);
         hit_timer.pause();
+        let mut jump_timer = Timer::from_seconds(0.12, TimerMode::Once);
+        jump_timer.pause();
         Self {
             hit_timer,
+            jump_timer,
             ai: typ.clone().into(),
+            hurt: false,
             typ,
             death_loot_table,
         }
     }
 
     pub fn hit_animation(&mut self) {
+        self.hurt = true;
         self.hit_timer.reset();
         self.hit_timer.unpause();
     }
 
-    pub fn get_loot(&self) -> (u64, Vec<ItemStack>) {
-        let path: PathBuf = self.death_loot_table.into();
+    pub fn get_loot<R: Rng>(&self, rng: &mut R) -> (u64, Vec<ItemStack>) {
+        let path: PathBuf = self.death_loot_table.clone().into();
         let loot_table_res = LootTable::read(&path);
         if let Some(loot_table) = loot_table_res {
-            loot_table.get_random()
+            loot_table.get_random(rng)
         } else {
             (0, vec![])
         }
@@ -96,6 +192,7 @@ pub struct MobBundle {
     object: MobObject,
     mob: Mob,
     stats: Stats,
+    effects: EffectsController,
     sprite: AnimatedSpriteBundle,
     collider: Collider,
     rigid_body: RigidBody,
@@ -103,106 +200,18 @@ pub struct MobBundle {
     mass: ColliderMassProperties,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum MobType {
     Passive,
     Neutral,
     Aggressive,
+    /// Shoots its [`MobTrait::projectile`] from afar
+    Ranged,
 }
 
-impl From<MobType> for Box<dyn MobAi> {
-    fn from(value: MobType) -> Self {
-        Box::new(match value {
-            MobType::Passive => PassiveDefaultMobAi::default(),
-            _ => PassiveDefaultMobAi::default(),
-        })
-    }
-}
-
-pub trait MobAi: Sync + Send {
-    fn update(
-        &mut self,
-        transform: &Transform,
-        controller: &mut KinematicCharacterController,
-        animation_controller: &mut AnimationController,
-        sprite: &mut TextureAtlasSprite,
-        stats: &mut Stats,
-        time: &Res<Time>,
-    );
-}
-
-#[derive(Default)]
-pub struct PassiveDefaultMobAi {
-    /// time until next wander (if wandering => timeout)
-    pub wander_timer: Timer,
-
-    /// x coord of the destination, None if not wandering
-    pub wandering_destination: Option<f32>,
-}
-
-impl PassiveDefaultMobAi {
-    const MAX_WANDER_DISTANCE: f32 = 15.;
-    const WANDERING_TIMEOUT: f32 = 10.;
-}
-
-impl MobAi for PassiveDefaultMobAi {
-    fn update(
-        &mut self,
-        transform: &Transform,
-        controller: &mut KinematicCharacterController,
-        animation_controller: &mut AnimationController,
-        sprite: &mut TextureAtlasSprite,
-        stats: &mut Stats,
-        time: &Res<Time>,
-    ) {
-        let mut moving_direction = Vec2::ZERO;
-        self.wander_timer.tick(time.delta());
-
-        if let Some(destination) = self.wandering_destination {
-            let destination_dist = destination - transform.translation.x;
-            let destination_reached = destination_dist == 0.0;
-            let timed_out = self.wander_timer.finished();
-
-            if destination_reached || timed_out {
-                self.wander_timer
-                    .set_duration(Duration::from_secs_f32(10. * random::<f32>() + 5.));
-                self.wander_timer.reset();
-                self.wandering_destination = None;
-                animation_controller.stop();
-            } else {
-                let movement = destination_dist.signum() * stats.speed * time.delta_seconds();
-
-                if animation_controller.animations.get("Walk").is_some()
-                    && animation_controller.current_animation != Some("Walk".into())
-                {
-                    animation_controller.play("Walk");
-                }
-
-                moving_direction.x += if destination_dist.abs() > movement.abs() {
-                    movement
-                } else {
-                    destination_dist
-                };
-            }
-        } else if self.wander_timer.finished() {
-            let direction = (random::<f32>() - 0.5) * BLOCK_SIZE * 2. * Self::MAX_WANDER_DISTANCE;
-
-            sprite.flip_x = direction < 0.;
-
-            self.wandering_destination = Some(transform.translation.x + direction);
-
-            self.wander_timer
-                .set_duration(Duration::from_secs_f32(Self::WANDERING_TIMEOUT));
-            self.wander_timer.reset();
-        }
-
-        moving_direction.y -= stats.mass * time.delta_seconds();
-        controller.translation = Some(moving_direction)
-    }
-}
-
-#[derive(Clone, Copy)]
-pub struct MobLootTable(pub &'static str);
+#[derive(Clone)]
+pub struct MobLootTable(pub String);
 impl From<MobLootTable> for PathBuf {
     fn from(val: MobLootTable) -> PathBuf {
         Path::new("mobs").join(format!("{}.json", val.0))
@@ -214,7 +223,7 @@ pub trait MobTrait: Component + Sized
 where
     MobObject: From<Self>,
 {
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
     fn texture(&self, animation: &str) -> PathBuf {
         Path::new("textures/mobs")
@@ -227,7 +236,7 @@ where
     fn typ(&self) -> MobType;
 
     fn death_loot_table(&self) -> MobLootTable {
-        MobLootTable(self.name())
+        MobLootTable(self.name().to_owned())
     }
 
     fn mob_obj(&self) -> Mob {
@@ -240,6 +249,22 @@ where
 
     fn default_stats(&self) -> Stats;
     fn collider(&self) -> Collider;
+
+    /// Experience given to the player when it kills the mob
+    fn xp(&self) -> u64 {
+        (self.default_stats().max_health / 2.).ceil() as u64
+    }
+
+    /// Fired at `target`, relative to `pos`, by ranged mobs
+    fn projectile(
+        &self,
+        _asset_server: &Res<AssetServer>,
+        _pos: Vec2,
+        _target: Vec2,
+    ) -> Option<ProjectileBundle> {
+        None
+    }
+
     fn bundle(self, asset_server: &Res<AssetServer>, position: Vec2) -> MobBundle {
         let (custom_size, anchor) = self.sprite_custom_size_and_anchor();
         let stats = self.default_stats();
@@ -265,6 +290,7 @@ where
             rigid_body: RigidBody::KinematicPositionBased,
             mass: ColliderMassProperties::Mass(stats.mass),
             stats,
+            effects: EffectsController::default(),
             controller: KinematicCharacterController::default(),
             object: self.into(),
         }
@@ -291,20 +317,3 @@ fn mob_hit(time: Res<Time>, mut query: Query<(&mut Mob, &mut TextureAtlasSprite)
         }
     }
 }
-
-fn load_mobs(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut event: EventReader<SaveData>,
-) {
-    for ev in event.read() {
-        let data = ev.read();
-        if let Some(world) = data.worlds.get(&data.current_world) {
-            world.mobs.iter().for_each(|mob| {
-                mob.data
-                    .clone()
-                    .spawn(&mut commands, &asset_server, mob.pos);
-            });
-        }
-    }
-}
@@ -0,0 +1,185 @@
+use super::{definition::MobDefinitions, Mob, MobTrait};
+use crate::{
+    boss::Boss,
+    day_night::WorldClock,
+    player::Player,
+    random::WorldRng,
+    world::{MobSpawnRates, World, BLOCK_SIZE},
+};
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+use std::time::Duration;
+
+/// Area of a map where mobs spawn, spawned from the "MobSpawn" rectangle objects of a Tiled map
+#[derive(Component)]
+pub struct SpawnZone {
+    /// World space area of the zone
+    pub rect: Rect,
+    /// [`None`] uses the spawn rates of the biome for the current time of day
+    pub spawn_rates: Option<MobSpawnRates>,
+    pub max_population: usize,
+    pub respawn_timer: Timer,
+    /// Whether the zone already got its first mobs, saved worlds only get respawns
+    pub populated: bool,
+}
+
+impl SpawnZone {
+    pub const DEFAULT_MAX_POPULATION: usize = 5;
+    pub const DEFAULT_RESPAWN_TIME: Duration = Duration::from_secs(30);
+}
+
+/// Top of every solid tile of a map that has no tile above it, inserted once the map is loaded
+#[derive(Component, Default)]
+pub struct GroundSpots(pub Vec<Vec2>);
+
+/// Mobs alive in a world, kept under [`World::max_mobs`] by the spawn zones
+#[derive(Component, Default)]
+pub struct MobPopulation {
+    pub alive: usize,
+}
+
+impl MobPopulation {
+    /// Mobs appear at most this far outside of the screen, so they're never seen popping in
+    /// and don't spawn where the player will never go
+    pub const SPAWN_MARGIN: f32 = 24. * BLOCK_SIZE;
+    /// Mobs this far outside of the screen are removed, the spawn zones replace them when the player comes back
+    pub const DESPAWN_MARGIN: f32 = 64. * BLOCK_SIZE;
+
+    /// # Returns
+    /// What the camera shows around the player, the player is used rather than the camera
+    /// because the camera only catches up on the next frame after a teleport
+    fn view(player: Vec2, projection: &OrthographicProjection) -> Rect {
+        Rect::from_center_size(player, projection.area.size())
+    }
+}
+
+pub fn count_mobs(
+    mut worlds: Query<&mut MobPopulation>,
+    mobs: Query<(), (With<Mob>, Without<Boss>)>,
+) {
+    for mut population in worlds.iter_mut() {
+        population.alive = mobs.iter().count();
+    }
+}
+
+pub fn despawn_far_mobs(
+    mut commands: Commands,
+    mobs: Query<(Entity, &Transform), (With<Mob>, Without<Boss>)>,
+    player_query: Query<&Transform, With<Player>>,
+    camera_query: Query<&OrthographicProjection, With<Camera2d>>,
+) {
+    let (Ok(player_transform), Ok(projection)) =
+        (player_query.get_single(), camera_query.get_single())
+    else {
+        return;
+    };
+
+    let area = MobPopulation::view(player_transform.translation.xy(), projection)
+        .inset(MobPopulation::DESPAWN_MARGIN);
+
+    for (entity, transform) in mobs.iter() {
+        if !area.contains(transform.translation.xy()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn populate_spawn_zones(
+    mut commands: Commands,
+    mut zones: Query<(&mut SpawnZone, &Parent)>,
+    mut maps: Query<(&GroundSpots, &World, &mut MobPopulation)>,
+    mobs: Query<&Transform, With<Mob>>,
+    player_query: Query<&Transform, With<Player>>,
+    camera_query: Query<&OrthographicProjection, With<Camera2d>>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    clock: Res<WorldClock>,
+    definitions: Res<MobDefinitions>,
+    mut rng: ResMut<WorldRng>,
+) {
+    let (Ok(player_transform), Ok(projection)) =
+        (player_query.get_single(), camera_query.get_single())
+    else {
+        return;
+    };
+
+    let view = MobPopulation::view(player_transform.translation.xy(), projection);
+    let area = view.inset(MobPopulation::SPAWN_MARGIN);
+
+    for (mut zone, parent) in zones.iter_mut() {
+        let Ok((ground_spots, world, mut population)) = maps.get_mut(parent.get()) else {
+            continue;
+        };
+
+        zone.respawn_timer.tick(time.delta());
+
+        let zone_population = mobs
+            .iter()
+            .filter(|transform| zone.rect.contains(transform.translation.xy()))
+            .count();
+
+        let mut missing = zone
+            .max_population
+            .saturating_sub(zone_population)
+            .min(world.max_mobs().saturating_sub(population.alive));
+        if zone.populated && zone.respawn_timer.just_finished() {
+            missing = missing.min(1);
+        } else if zone.populated {
+            missing = 0;
+        }
+
+        // Off screen but close enough to the player
+        let spots = ground_spots
+            .0
+            .iter()
+            .filter(|spot| {
+                zone.rect.contains(**spot) && area.contains(**spot) && !view.contains(**spot)
+            })
+            .collect::<Vec<_>>();
+
+        if spots.is_empty() {
+            continue;
+        }
+        zone.populated = true;
+
+        let biome_spawn_rates;
+        let spawn_rates = match (&zone.spawn_rates, world) {
+            (Some(spawn_rates), _) => spawn_rates,
+            (None, World::Biome(biome)) => {
+                biome_spawn_rates = biome.spawn_rates(clock.time_of_day(), &definitions);
+                &biome_spawn_rates
+            }
+            (None, World::Dungeon(_)) => continue,
+        };
+
+        while missing > 0 {
+            // A group that can't hold a mob would never lower `missing`
+            let rates = spawn_rates
+                .get_random(&mut *rng)
+                .into_iter()
+                .filter(|rate| !rate.group.is_empty() && *rate.group.end() > 0)
+                .collect::<Vec<_>>();
+            if rates.is_empty() {
+                break;
+            }
+
+            for rate in rates {
+                let count = (rng.gen_range(rate.group) as usize).min(missing);
+                if count == 0 {
+                    continue;
+                }
+                let spot = **spots.choose(&mut *rng).unwrap();
+
+                for _ in 0..count {
+                    rate.mob.clone().spawn(
+                        &mut commands,
+                        &asset_server,
+                        spot + Vec2::new(0., BLOCK_SIZE),
+                    );
+                }
+                missing -= count;
+                population.alive += count;
+            }
+        }
+    }
+}
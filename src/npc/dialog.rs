@@ -254,8 +254,7 @@ fn dialog_update(
         }
 
         // Si il n'y a pas de choix disponibles
-        if !current_line_opt.is_some_and(|c| !c.choices.is_empty()) {
-
+        if current_line_opt.is_none_or(|c| c.choices.is_empty()) {
             // Et qu'on clique sur l'ui
             if *text_container_interaction == Interaction::Pressed {
                 // Aller à la prochaine ligne
@@ -281,7 +280,7 @@ fn dialog_update(
                             // Et on change le shop ouvert actuel
                             *current_shop = CurrentShop { shop };
 
-                            // On passe a la prochaine ligne 
+                            // On passe a la prochaine ligne
                             // pour que l'utilisateur revienne sur le dialogue au bon moment un fois le shop fermé
                             next_line(&mut current_dialog_res);
 
@@ -304,7 +303,7 @@ fn dialog_update(
         let chars: Vec<char> = dialog_text.full_text.chars().collect();
         // Numéro du caractère actuel arrondi
         let current_char_index = dialog_text.current_index as usize;
-        
+
         // Tant qu'on a pas fini d'afficher tous les caractères
         // Pas besoin de `while` la fonction se répète déjà et on bloque le thread si on mettait un `while`
         if current_char_index <= chars.len() {
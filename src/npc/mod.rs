@@ -78,7 +78,7 @@ impl Npc {
     }
 
     pub fn get_texture(&self) -> PathBuf {
-        Path::new("textures/npc").join(&format!("{}.png", self.to_string()))
+        Path::new("textures/npc").join(format!("{}.png", self))
     }
 }
 
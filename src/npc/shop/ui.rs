@@ -186,12 +186,9 @@ fn shop_ui_update(
     };
 
     //Update Shop
-    match close_shop_q.single() {
-        Interaction::Pressed => {
-            current_shop.shop = None;
-            return;
-        }
-        _ => {}
+    if *close_shop_q.single() == Interaction::Pressed {
+        current_shop.shop = None;
+        return;
     }
 
     for (interaction, tab_button, mut background_color) in tab_button_q.iter_mut() {
@@ -1,13 +1,18 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use crate::{
     interactable::Interactable,
     items::{list::unprocessed_ore::UnprocessedOre, stack::ItemStack},
-    random::RandomWeightedTable,
+    random::{RandomWeightedTable, WorldRng},
+    state::AppState,
     world::BLOCK_SIZE,
 };
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
@@ -51,7 +56,7 @@ impl Ore {
     }
 
     pub fn get_texture(&self) -> PathBuf {
-        Path::new("textures/ores").join(format!("{}.png", self.to_string()))
+        Path::new("textures/ores").join(format!("{}.png", self))
     }
 }
 
@@ -65,24 +70,30 @@ pub enum OreType {
 pub struct MinableOre {
     pub current_ore: Ore,
     pub random_ore_table: RandomWeightedTable<Ore>,
+    /// Id of the Tiled object the ore was spawned from
+    pub object_id: u32,
+    pub regrow_time: Duration,
+    /// When the ore grows back, [`None`] if it hasn't been mined
+    pub regrow_at: Option<DateTime<Local>>,
+}
+
+impl MinableOre {
+    /// Time for a mined ore to grow back when the map doesn't specify a `regrow_time`
+    pub const DEFAULT_REGROW_TIME: Duration = Duration::from_secs(600);
+
+    pub fn is_mined(&self) -> bool {
+        self.regrow_at.is_some()
+    }
 }
 
 #[derive(Bundle)]
 pub struct MinableOreBundle {
     pub ore: MinableOre,
     pub sprite: SpriteBundle,
-    pub collider: Collider,
-    pub interactable: Interactable,
 }
 
 impl MinableOreBundle {
-    pub fn new(
-        table: RandomWeightedTable<Ore>,
-        pos: Vec2,
-        rotation: f32,
-        asset_server: &AssetServer,
-    ) -> Self {
-        let current_ore = table.get_random().first().unwrap().clone();
+    pub fn new(ore: MinableOre, pos: Vec2, rotation: f32, asset_server: &AssetServer) -> Self {
         let mut transform = Transform::from_translation(pos.extend(28.));
         transform.rotate_z(rotation);
 
@@ -93,38 +104,105 @@ impl MinableOreBundle {
                     ..Default::default()
                 },
                 transform,
-                texture: asset_server.load(current_ore.get_texture()),
+                texture: asset_server.load(ore.current_ore.get_texture()),
+                visibility: if ore.is_mined() {
+                    Visibility::Hidden
+                } else {
+                    Visibility::Inherited
+                },
                 ..Default::default()
             },
-            ore: MinableOre {
-                current_ore,
-                random_ore_table: table,
-            },
-            collider: Collider::capsule_y(BLOCK_SIZE / 4., BLOCK_SIZE / 2.),
-            interactable: Interactable::new("player.actions.mine"),
+            ore,
         }
     }
 }
 
+/// Components only present while the ore can be mined
+fn minable_components() -> (Collider, Interactable) {
+    (
+        Collider::capsule_y(BLOCK_SIZE / 4., BLOCK_SIZE / 2.),
+        Interactable::new("player.actions.mine"),
+    )
+}
+
 pub struct OrePlugin;
 impl Plugin for OrePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, minable_ore_update);
+        app.add_systems(
+            Update,
+            (setup_minable_ore, minable_ore_update, regrow_ores).run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+fn setup_minable_ore(
+    mut commands: Commands,
+    query: Query<(Entity, &MinableOre), Added<MinableOre>>,
+) {
+    for (entity, ore) in query.iter() {
+        if !ore.is_mined() {
+            commands.entity(entity).insert(minable_components());
+        }
     }
 }
 
 fn minable_ore_update(
     mut commands: Commands,
-    query: Query<(Entity, &MinableOre, &Interactable, &Transform)>,
+    mut query: Query<(
+        Entity,
+        &mut MinableOre,
+        &Interactable,
+        &Transform,
+        &mut Visibility,
+    )>,
     asset_server: Res<AssetServer>,
 ) {
-    for (entity, ore, interactable, transform) in query.iter() {
+    for (entity, mut ore, interactable, transform, mut visibility) in query.iter_mut() {
         if interactable.just_pressed() {
-            commands.entity(entity).despawn_recursive();
+            // An ore that couldn't grow back would stay hidden forever
+            let regrow_at = [ore.regrow_time, MinableOre::DEFAULT_REGROW_TIME]
+                .into_iter()
+                .filter_map(|regrow_time| chrono::Duration::from_std(regrow_time).ok())
+                .find_map(|regrow_time| Local::now().checked_add_signed(regrow_time));
+            let Some(regrow_at) = regrow_at else {
+                continue;
+            };
+
             commands.spawn(
                 ItemStack::new_one(UnprocessedOre(ore.current_ore.clone()))
                     .bundle(&asset_server, transform.translation.xy()),
             );
+
+            // Keep the entity around so its state ends up in the save
+            ore.regrow_at = Some(regrow_at);
+            *visibility = Visibility::Hidden;
+            commands
+                .entity(entity)
+                .remove::<(Collider, Interactable)>()
+                .despawn_descendants();
+        }
+    }
+}
+
+fn regrow_ores(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut MinableOre, &mut Handle<Image>, &mut Visibility)>,
+    asset_server: Res<AssetServer>,
+    mut rng: ResMut<WorldRng>,
+) {
+    let now = Local::now();
+
+    for (entity, mut ore, mut texture, mut visibility) in query.iter_mut() {
+        if ore.regrow_at.is_some_and(|regrow_at| regrow_at <= now) {
+            let Some(new_ore) = ore.random_ore_table.get_random(&mut *rng).first().cloned() else {
+                continue;
+            };
+
+            *texture = asset_server.load(new_ore.get_texture());
+            ore.current_ore = new_ore;
+            ore.regrow_at = None;
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).insert(minable_components());
         }
     }
 }
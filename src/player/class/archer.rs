@@ -4,17 +4,18 @@ use super::{can_attack, is_of_class, PlayerClass};
 use crate::{
     animation::{AnimatedSpriteBundle, Animation, AnimationController},
     animation_maker,
+    damage::DamageType,
+    effects::Effect,
     gui::settings::{keybinds::Keybind, Settings},
-    mob::Mob,
     player::{sprite_vec, Player},
-    stats::Stats,
+    projectile::{Faction, Projectile, ProjectileBundle},
     world::BLOCK_SIZE,
 };
 use bevy::{prelude::*, sprite::Anchor, utils::hashbrown::HashMap};
 use bevy_rapier2d::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct Archer;
 
 impl PlayerClass for Archer {
@@ -31,7 +32,9 @@ impl Plugin for Archer {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            ((special_attacks).run_if(can_attack), arrow_update).run_if(is_of_class::<Self>),
+            special_attacks
+                .run_if(can_attack)
+                .run_if(is_of_class::<Self>),
         );
     }
 }
@@ -42,6 +45,7 @@ fn special_attacks(
     keyboard_input: Res<Input<KeyCode>>,
     mouse_input: Res<Input<MouseButton>>,
     mut query: Query<(
+        Entity,
         &mut Player,
         &mut AnimationController,
         &Transform,
@@ -49,7 +53,8 @@ fn special_attacks(
     )>,
     asset_server: Res<AssetServer>,
 ) {
-    let Ok((mut player, mut animation_controller, transform, sprite)) = query.get_single_mut()
+    let Ok((entity, mut player, mut animation_controller, transform, sprite)) =
+        query.get_single_mut()
     else {
         return;
     };
@@ -73,6 +78,7 @@ fn special_attacks(
             commands.spawn(Arrow::bundle(
                 name,
                 transform.translation.xy(),
+                entity,
                 sprite,
                 &asset_server,
             ));
@@ -80,75 +86,25 @@ fn special_attacks(
     }
 }
 
-fn arrow_update(
-    mut commands: Commands,
-    rapier_context: Res<RapierContext>,
-    player_query: Query<Entity, With<Player>>,
-    mut query: Query<(
-        Entity,
-        &TextureAtlasSprite,
-        &mut Transform,
-        &Collider,
-        &mut Arrow,
-        &mut AnimationController,
-    )>,
-    mut mob_query: Query<(&mut Mob, &mut Stats)>,
-    time: Res<Time>,
-) {
-    let Ok(player_entity) = player_query.get_single() else {
-        return;
-    };
-
-    for (entity, sprite, mut transform, collider, mut arrow, mut animation_controller) in
-        query.iter_mut()
-    {
-        if !arrow.hit {
-            let movement = sprite_vec(sprite).x * Arrow::SPEED * time.delta_seconds();
-            transform.translation.x += movement;
-            arrow.traveled_dist += movement;
-
-            if arrow.traveled_dist >= Arrow::MAX_TRAVEL_DIST {
-                commands.entity(entity).despawn();
-                continue;
-            }
-
-            rapier_context.intersections_with_shape(
-                transform.translation.xy(),
-                0.0,
-                collider,
-                QueryFilter {
-                    predicate: Some(&|e| e != entity && e != player_entity),
-                    ..Default::default()
-                },
-                |hit_entity| {
-                    arrow.hit = true;
-                    if let Ok((mut mob, mut stats)) = mob_query.get_mut(hit_entity) {
-                        mob.hit_animation();
-                        stats.take_damage(15.);
-                    }
-
-                    true
-                },
-            );
-        } else {
-            animation_controller.tick(&time);
-            if animation_controller.just_finished.is_some() {
-                commands.entity(entity).despawn();
-            }
-        }
-    }
-}
-
 #[derive(Component, Default)]
-pub struct Arrow {
-    hit: bool,
-    traveled_dist: f32,
-}
+pub struct Arrow;
 
 impl Arrow {
     const SPEED: f32 = 400.;
     const MAX_TRAVEL_DIST: f32 = BLOCK_SIZE * 400.;
     const MANA_COST: f32 = 40.;
+    const DAMAGE: f32 = 15.;
+
+    /// # Returns
+    /// The type of the damage of an arrow, and the effect it gives for some seconds
+    fn element(name: &str) -> (DamageType, Effect, f32) {
+        match name {
+            "Fire" => (DamageType::Fire, Effect::Burning, 4.),
+            "Magic" => (DamageType::Magic, Effect::Slowed, 3.),
+            "Poison" => (DamageType::Poison, Effect::Poisoned, 6.),
+            _ => panic!(),
+        }
+    }
 
     pub fn animations(asset_server: &Res<AssetServer>) -> HashMap<String, Animation> {
         let get_texture =
@@ -169,33 +125,46 @@ impl Arrow {
     pub fn bundle(
         name: &str,
         pos: Vec2,
+        owner: Entity,
         player_sprite: &TextureAtlasSprite,
         asset_server: &Res<AssetServer>,
     ) -> ArrowBundle {
         let mut animation_controller = AnimationController::new(Self::animations(asset_server));
         animation_controller.play(name);
+        let (damage_type, effect, seconds) = Self::element(name);
 
         ArrowBundle {
-            arrow: Arrow::default(),
-            collider: Collider::capsule_x(Player::SIZE / 8., Player::SIZE / 10.),
-            rigid_body: RigidBody::Fixed,
-            sprite: AnimatedSpriteBundle {
-                animation_controller,
-                sprite: SpriteSheetBundle {
-                    sprite: TextureAtlasSprite {
-                        flip_x: player_sprite.flip_x,
-                        custom_size: Some(Vec2::splat(Player::SIZE)),
-                        anchor: if name == "Poison" {
-                            Anchor::Custom(Vec2::new(0.0, -0.25))
-                        } else {
-                            Anchor::default()
+            arrow: Arrow,
+            projectile: ProjectileBundle {
+                projectile: Projectile::new(
+                    sprite_vec(player_sprite) * Self::SPEED,
+                    Self::DAMAGE,
+                    Faction::Player,
+                )
+                .with_damage_type(damage_type)
+                .with_effect(effect, seconds, 0)
+                .with_lifetime(Self::MAX_TRAVEL_DIST / Self::SPEED)
+                .with_owner(owner),
+                collider: Collider::capsule_x(Player::SIZE / 8., Player::SIZE / 10.),
+                sensor: Sensor,
+                sprite: AnimatedSpriteBundle {
+                    animation_controller,
+                    sprite: SpriteSheetBundle {
+                        sprite: TextureAtlasSprite {
+                            flip_x: player_sprite.flip_x,
+                            custom_size: Some(Vec2::splat(Player::SIZE)),
+                            anchor: if name == "Poison" {
+                                Anchor::Custom(Vec2::new(0.0, -0.25))
+                            } else {
+                                Anchor::default()
+                            },
+                            ..Default::default()
                         },
+                        transform: Transform::from_translation(
+                            (pos + sprite_vec(player_sprite) * Player::SIZE / 4.).extend(15.0),
+                        ),
                         ..Default::default()
                     },
-                    transform: Transform::from_translation(
-                        (pos + sprite_vec(player_sprite) * Player::SIZE / 4.).extend(15.0),
-                    ),
-                    ..Default::default()
                 },
             },
         }
@@ -205,7 +174,5 @@ impl Arrow {
 #[derive(Bundle)]
 pub struct ArrowBundle {
     arrow: Arrow,
-    collider: Collider,
-    rigid_body: RigidBody,
-    sprite: AnimatedSpriteBundle,
+    projectile: ProjectileBundle,
 }
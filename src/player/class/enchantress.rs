@@ -1,10 +1,10 @@
 use crate::{
     animation::AnimationController,
     animation_maker,
+    damage::{DamageEvent, DamageType},
     gui::settings::{keybinds::Keybind, Settings},
     mob::Mob,
     player::{cast_collider, sprite_vec, Player},
-    stats::Stats,
 };
 
 use super::{is_of_class, PlayerClass};
@@ -12,7 +12,7 @@ use bevy::{prelude::*, sprite::Anchor, utils::hashbrown::HashMap};
 use bevy_rapier2d::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct Enchantress;
 
 impl Enchantress {
@@ -70,7 +70,8 @@ fn special_attacks(
     mouse_input: Res<Input<MouseButton>>,
     rapier_context: Res<RapierContext>,
     time: Res<Time>,
-    mut mob_query: Query<(&mut Stats, &mut Mob)>,
+    mob_query: Query<(), With<Mob>>,
+    mut damage_event: EventWriter<DamageEvent>,
 ) {
     if let Ok((entity, mut player, transform, mut sprite, mut animation_controller)) =
         player_query.get_single_mut()
@@ -84,9 +85,11 @@ fn special_attacks(
                 hitbox_translation,
                 &rapier_context,
                 |hit_entity| {
-                    if let Ok((mut stats, mut mob)) = mob_query.get_mut(hit_entity) {
-                        stats.take_damage(damage);
-                        mob.hit_animation();
+                    if mob_query.contains(hit_entity) {
+                        damage_event.send(
+                            DamageEvent::new(Some(entity), hit_entity, damage)
+                                .with_type(DamageType::Magic),
+                        );
                     }
                     true
                 },
@@ -9,11 +9,11 @@ use strum_macros::{EnumCount, EnumIter};
 use crate::{
     animation::{Animation, AnimationController},
     animation_maker,
+    damage::DamageEvent,
     gui::settings::{keybinds::Keybind, Settings},
     lang::Lang,
     misc::read_img,
     mob::Mob,
-    stats::Stats,
 };
 
 use self::{
@@ -76,7 +76,7 @@ pub trait PlayerClass: Sync + Send + Default + Into<PlayerClasses> {
     }
 }
 
-#[derive(Serialize, Deserialize, EnumIter, EnumCount, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, EnumIter, EnumCount, Clone, PartialEq, Eq, Debug)]
 #[enum_dispatch(PlayerClass)]
 pub enum PlayerClasses {
     Archer(Archer),
@@ -127,7 +127,8 @@ pub fn swords_user_special_attacks(
         &TextureAtlasSprite,
         &mut AnimationController,
     )>,
-    mut mob_query: Query<(&mut Mob, &mut Stats)>,
+    mob_query: Query<(), With<Mob>>,
+    mut damage_event: EventWriter<DamageEvent>,
     rapier_context: Res<RapierContext>,
     settings: Res<Settings>,
     keyboard_input: Res<Input<KeyCode>>,
@@ -162,9 +163,12 @@ pub fn swords_user_special_attacks(
                         shape_pos,
                         &rapier_context,
                         |hit_entity| {
-                            if let Ok((mut mob, mut stats)) = mob_query.get_mut(hit_entity) {
-                                mob.hit_animation();
-                                stats.take_damage(damage);
+                            if mob_query.contains(hit_entity) {
+                                damage_event.send(DamageEvent::new(
+                                    Some(entity),
+                                    hit_entity,
+                                    damage,
+                                ));
                             }
 
                             true
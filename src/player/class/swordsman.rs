@@ -2,7 +2,7 @@ use super::{can_attack, is_of_class, swords_user_special_attacks, PlayerClass, S
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct Swordsman;
 
 impl PlayerClass for Swordsman {
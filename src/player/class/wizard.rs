@@ -4,10 +4,10 @@ use crate::{
     animation::{
         AnimatedSpriteBundle, Animation, AnimationController, AnimationDirection, AnimationMode,
     },
+    damage::DamageType,
     gui::settings::{keybinds::Keybind, Settings},
-    mob::Mob,
     player::{sprite_vec, Player},
-    stats::Stats,
+    projectile::{Faction, Projectile, ProjectileBundle},
     world::BLOCK_SIZE,
 };
 
@@ -16,7 +16,7 @@ use bevy::{prelude::*, sprite::Anchor, utils::hashbrown::HashMap};
 use bevy_rapier2d::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct Wizard;
 
 impl PlayerClass for Wizard {
@@ -33,7 +33,9 @@ impl Plugin for Wizard {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            ((special_attacks).run_if(can_attack), charge_update).run_if(is_of_class::<Self>),
+            special_attacks
+                .run_if(can_attack)
+                .run_if(is_of_class::<Self>),
         );
     }
 }
@@ -44,6 +46,7 @@ fn special_attacks(
     keyboard_input: Res<Input<KeyCode>>,
     mouse_input: Res<Input<MouseButton>>,
     mut query: Query<(
+        Entity,
         &mut Player,
         &mut AnimationController,
         &Transform,
@@ -51,7 +54,8 @@ fn special_attacks(
     )>,
     asset_server: Res<AssetServer>,
 ) {
-    let Ok((mut player, mut animation_controller, transform, sprite)) = query.get_single_mut()
+    let Ok((entity, mut player, mut animation_controller, transform, sprite)) =
+        query.get_single_mut()
     else {
         return;
     };
@@ -79,6 +83,7 @@ fn special_attacks(
             commands.spawn(Charge::bundle(
                 i,
                 transform.translation.xy(),
+                entity,
                 class,
                 sprite,
                 &asset_server,
@@ -87,75 +92,14 @@ fn special_attacks(
     }
 }
 
-fn charge_update(
-    mut commands: Commands,
-    rapier_context: Res<RapierContext>,
-    player_query: Query<Entity, With<Player>>,
-    mut query: Query<(
-        Entity,
-        &TextureAtlasSprite,
-        &mut Transform,
-        &Collider,
-        &mut Charge,
-        &mut AnimationController,
-    )>,
-    mut mob_query: Query<(&mut Mob, &mut Stats)>,
-    time: Res<Time>,
-) {
-    let Ok(player_entity) = player_query.get_single() else {
-        return;
-    };
-
-    for (entity, sprite, mut transform, collider, mut charge, mut animation_controller) in
-        query.iter_mut()
-    {
-        if !charge.hit {
-            let movement = sprite_vec(sprite).x * Charge::SPEED * time.delta_seconds();
-            transform.translation.x += movement;
-            charge.traveled_dist += movement;
-
-            if charge.traveled_dist >= Charge::MAX_TRAVEL_DIST {
-                commands.entity(entity).despawn();
-                continue;
-            }
-
-            rapier_context.intersections_with_shape(
-                transform.translation.xy(),
-                0.0,
-                collider,
-                QueryFilter {
-                    predicate: Some(&|e| e != entity && e != player_entity),
-                    ..Default::default()
-                },
-                |hit_entity| {
-                    charge.hit = true;
-                    if let Ok((mut mob, mut stats)) = mob_query.get_mut(hit_entity) {
-                        mob.hit_animation();
-                        stats.take_damage(15.);
-                    }
-
-                    true
-                },
-            );
-        } else {
-            animation_controller.tick(&time);
-            if animation_controller.just_finished.is_some() {
-                commands.entity(entity).despawn();
-            }
-        }
-    }
-}
-
 #[derive(Component, Default)]
-pub struct Charge {
-    hit: bool,
-    traveled_dist: f32,
-}
+pub struct Charge;
 
 impl Charge {
     const SPEED: f32 = 400.;
     const MAX_TRAVEL_DIST: f32 = BLOCK_SIZE * 400.;
     const MANA_COST: f32 = 40.;
+    const DAMAGE: f32 = 15.;
 
     pub fn animations(
         class: &Wizard,
@@ -180,6 +124,7 @@ impl Charge {
     pub fn bundle(
         i: u32,
         pos: Vec2,
+        owner: Entity,
         class: &Wizard,
         player_sprite: &TextureAtlasSprite,
         asset_server: &Res<AssetServer>,
@@ -189,22 +134,32 @@ impl Charge {
         animation_controller.play("Blow");
 
         ChargeBundle {
-            charge: Charge::default(),
-            collider: Collider::capsule_x(Player::SIZE / 10., Player::SIZE / 10.),
-            rigid_body: RigidBody::Fixed,
-            sprite: AnimatedSpriteBundle {
-                animation_controller,
-                sprite: SpriteSheetBundle {
-                    sprite: TextureAtlasSprite {
-                        flip_x: player_sprite.flip_x,
-                        custom_size: Some(Vec2::splat(Player::SIZE)),
-                        anchor: Anchor::default(),
+            charge: Charge,
+            projectile: ProjectileBundle {
+                projectile: Projectile::new(
+                    sprite_vec(player_sprite) * Self::SPEED,
+                    Self::DAMAGE,
+                    Faction::Player,
+                )
+                .with_damage_type(DamageType::Magic)
+                .with_lifetime(Self::MAX_TRAVEL_DIST / Self::SPEED)
+                .with_owner(owner),
+                collider: Collider::capsule_x(Player::SIZE / 10., Player::SIZE / 10.),
+                sensor: Sensor,
+                sprite: AnimatedSpriteBundle {
+                    animation_controller,
+                    sprite: SpriteSheetBundle {
+                        sprite: TextureAtlasSprite {
+                            flip_x: player_sprite.flip_x,
+                            custom_size: Some(Vec2::splat(Player::SIZE)),
+                            anchor: Anchor::default(),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(
+                            (pos + sprite_vec(player_sprite) * Player::SIZE / 4.).extend(15.0),
+                        ),
                         ..Default::default()
                     },
-                    transform: Transform::from_translation(
-                        (pos + sprite_vec(player_sprite) * Player::SIZE / 4.).extend(15.0),
-                    ),
-                    ..Default::default()
                 },
             },
         }
@@ -214,7 +169,5 @@ impl Charge {
 #[derive(Bundle)]
 pub struct ChargeBundle {
     charge: Charge,
-    collider: Collider,
-    rigid_body: RigidBody,
-    sprite: AnimatedSpriteBundle,
+    projectile: ProjectileBundle,
 }
@@ -0,0 +1,212 @@
+use super::Player;
+use crate::{
+    gui::settings::Settings,
+    interactable::Interactable,
+    portal::{is_travelling, place_player, TravelEvent},
+    random::WorldRng,
+    save::{CheckpointSave, CurrentSave, DeathSave, SaveWorldEvent},
+    state::AppState,
+    stats::Stats,
+};
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+pub struct DeathPlugin;
+impl Plugin for DeathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayerDeathEvent>()
+            .add_event::<RespawnEvent>()
+            .init_resource::<SaveAfterRespawn>()
+            .add_systems(
+                Update,
+                (
+                    checkpoint_update,
+                    die,
+                    save_respawned
+                        .after(place_player)
+                        .run_if(not(is_travelling)),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(Update, respawn.run_if(in_state(AppState::Dead)));
+    }
+}
+
+/// Sent once the "Dead" animation of the player is over
+#[derive(Event)]
+pub struct PlayerDeathEvent;
+
+/// Asks to leave the death screen
+#[derive(Event)]
+pub struct RespawnEvent;
+
+/// Whether the world must be saved once the respawned player is placed
+#[derive(Resource, Default)]
+struct SaveAfterRespawn(bool);
+
+/// What dying costs, set in the settings file
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DeathPenalty {
+    /// Part of the money lost, from 0 to 1
+    pub money: f32,
+    /// Chance of each ressource stack to be left where the player died, from 0 to 1
+    pub item_drop_chance: f32,
+}
+
+impl Default for DeathPenalty {
+    fn default() -> Self {
+        Self {
+            money: 0.1,
+            item_drop_chance: 0.25,
+        }
+    }
+}
+
+/// Where the player respawns once reached, spawned from the "Checkpoint" objects of a Tiled map
+#[derive(Component)]
+pub struct Checkpoint {
+    /// Also the name of the [`SpawnPoint`](crate::portal::SpawnPoint) of the checkpoint
+    pub name: String,
+}
+
+/// Whether the player can still act, it can't while playing its "Dead" animation
+pub fn is_alive(query: Query<&Stats, With<Player>>) -> bool {
+    query.get_single().map_or(true, |stats| stats.health > 0.)
+}
+
+fn checkpoint_update(
+    query: Query<(&Checkpoint, &Interactable)>,
+    mut player_query: Query<&mut Stats, With<Player>>,
+    mut current_save: ResMut<CurrentSave>,
+    mut save_event: EventWriter<SaveWorldEvent>,
+) {
+    for (checkpoint, interactable) in query.iter() {
+        if !interactable.just_pressed() {
+            continue;
+        }
+
+        let Some(save_data) = &mut current_save.0 else {
+            return;
+        };
+
+        let world = save_data.data.current_world.clone();
+        info!(
+            "Checkpoint {} of {}.{} reached",
+            checkpoint.name,
+            world.get_type(),
+            world.name()
+        );
+        save_data.data.checkpoint = Some(CheckpointSave {
+            world,
+            spawn_point: checkpoint.name.clone(),
+        });
+
+        // Resting at a checkpoint heals
+        if let Ok(mut stats) = player_query.get_single_mut() {
+            stats.health = stats.max_health;
+        }
+
+        save_event.send(SaveWorldEvent);
+    }
+}
+
+/// Shows the death screen, the penalty is only taken on respawn so closing the game
+/// on the death screen keeps the last save
+fn die(
+    mut death_event: EventReader<PlayerDeathEvent>,
+    mut state_change: ResMut<NextState<AppState>>,
+) {
+    if death_event.read().count() == 0 {
+        return;
+    }
+
+    state_change.set(AppState::Dead);
+}
+
+/// Takes the penalty where the player died and brings it back to its last checkpoint,
+/// or to the spawn of the world it died in
+fn respawn(
+    mut commands: Commands,
+    mut respawn_event: EventReader<RespawnEvent>,
+    mut player_query: Query<(&mut Player, &mut Stats, &Transform)>,
+    mut current_save: ResMut<CurrentSave>,
+    mut travel_event: EventWriter<TravelEvent>,
+    mut state_change: ResMut<NextState<AppState>>,
+    mut save_after_respawn: ResMut<SaveAfterRespawn>,
+    settings: Res<Settings>,
+    asset_server: Res<AssetServer>,
+    mut rng: ResMut<WorldRng>,
+) {
+    if respawn_event.read().count() == 0 {
+        return;
+    }
+
+    let Some(save_data) = &mut current_save.0 else {
+        return;
+    };
+
+    let Ok((mut player, mut stats, transform)) = player_query.get_single_mut() else {
+        return;
+    };
+    stats.health = stats.max_health;
+    let pos = transform.translation.xy();
+    let penalty = &settings.death_penalty;
+
+    let money_lost = (player.money.get() as f32 * penalty.money.clamp(0., 1.)) as u64;
+    player.money.try_remove(money_lost);
+
+    // The dropped stacks stay in the world until the player comes back for them
+    let mut items_dropped = 0;
+    for slot in player.inventory.ressources.iter_mut() {
+        if slot.item.is_none() || !rng.gen_bool(penalty.item_drop_chance.clamp(0., 1.) as f64) {
+            continue;
+        }
+
+        if let Some(stack) = slot.item.take() {
+            commands.spawn(stack.bundle(&asset_server, pos));
+            items_dropped += 1;
+        }
+    }
+
+    let save = &mut save_data.data;
+    info!(
+        "Player died in {}.{}, losing {money_lost} coins and {items_dropped} stacks",
+        save.current_world.get_type(),
+        save.current_world.name()
+    );
+    save.deaths.push(DeathSave {
+        world: save.current_world.clone(),
+        pos,
+        date: chrono::offset::Local::now(),
+        money_lost,
+        items_dropped,
+    });
+
+    let (target, spawn_point) = match &save.checkpoint {
+        Some(checkpoint) => (checkpoint.world.clone(), checkpoint.spawn_point.clone()),
+        None => (
+            save.current_world.clone(),
+            save.current_world.spawn_point().to_owned(),
+        ),
+    };
+
+    travel_event.send(TravelEvent {
+        target,
+        spawn_point,
+    });
+    save_after_respawn.0 = true;
+    state_change.set(AppState::InGame);
+}
+
+/// Saves once the player stands at its respawn point, so the penalty is never saved without it
+fn save_respawned(
+    mut save_after_respawn: ResMut<SaveAfterRespawn>,
+    mut save_event: EventWriter<SaveWorldEvent>,
+) {
+    if save_after_respawn.0 {
+        save_after_respawn.0 = false;
+        save_event.send(SaveWorldEvent);
+    }
+}
@@ -18,7 +18,7 @@ impl Plugin for InventoryPlugin {
     }
 }
 
-#[derive(Default, Component, Deserialize, Serialize, Clone, Reflect)]
+#[derive(Default, Component, Deserialize, Serialize, Clone, Reflect, PartialEq, Debug)]
 pub struct Inventory {
     pub ressources: [Slot; Self::RESSOURCE_COUNT],
     pub armor: [Slot; Self::ARMOR_COUNT],
@@ -55,6 +55,22 @@ impl Inventory {
         }
     }
 
+    /// Armor and accessories, the slots whose items give their bonuses
+    fn equipment(&self) -> impl Iterator<Item = &ItemStack> {
+        self.armor
+            .iter()
+            .chain(self.accessories.iter())
+            .filter_map(|slot| slot.item.as_ref())
+    }
+
+    pub fn equipment_strength(&self) -> f32 {
+        self.equipment().map(|stack| stack.item.strength()).sum()
+    }
+
+    pub fn equipment_def(&self) -> f32 {
+        self.equipment().map(|stack| stack.item.def()).sum()
+    }
+
     /// to check if all the stack was consumed use [`optional_item_stack.is_none()`]
     pub fn push_item_stack(
         &mut self,
@@ -158,7 +174,7 @@ impl Default for SlotType {
     }
 }
 
-#[derive(Default, Deserialize, Serialize, Clone, Reflect)]
+#[derive(Default, Deserialize, Serialize, Clone, Reflect, PartialEq, Debug)]
 pub struct Slot {
     pub item: Option<ItemStack>,
 }
@@ -3,9 +3,15 @@ use bevy::prelude::*;
 use crate::{
     gui::{make_menu, settings::Settings, styles::text_style},
     items::{item::ItemTrait, stack::ItemStack},
+    lang::Lang,
     npc::dialog::in_dialog,
-    player::{class::PlayerClass, sprite_vec, Player},
+    player::{
+        class::PlayerClass,
+        level::{spawn_level_ui, PlayerLevel},
+        sprite_vec, Player,
+    },
     state::AppState,
+    stats::Stats,
 };
 
 use super::{Inventory, Slot, SlotType};
@@ -54,18 +60,22 @@ struct MovingStack(pub Option<ItemStack>);
 
 fn spawn_inventory(
     mut commands: Commands,
-    player_query: Query<&Player>,
+    player_query: Query<(&Player, &PlayerLevel, &Stats)>,
     asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
 ) {
-    if let Ok(player) = player_query.get_single() {
+    if let Ok((player, level, stats)) = player_query.get_single() {
         //Overlay darken bg
         make_menu(
             &mut commands,
             Color::BLACK.with_a(0.5).into(),
             InventoryMenu,
-            |builder| spawn_inventory_ui(builder, &asset_server, player),
+            |builder| {
+                spawn_inventory_ui(builder, &asset_server, player);
+                spawn_level_ui(builder, &asset_server, &lang, level, stats, &player.mana);
+            },
             None,
-            Some(FlexDirection::Column),
+            Some(FlexDirection::Row),
         );
     }
 }
@@ -150,7 +160,7 @@ pub fn spawn_inventory_ui(builder: &mut ChildBuilder, asset_server: &AssetServer
                                     builder
                                         .spawn(TextBundle::from_section(
                                             player.money.get().to_string(),
-                                            text_style(&asset_server),
+                                            text_style(asset_server),
                                         ))
                                         .insert(MoneyDisplay);
 
@@ -171,7 +181,7 @@ pub fn spawn_inventory_ui(builder: &mut ChildBuilder, asset_server: &AssetServer
                                 FlexDirection::Column,
                                 builder,
                                 "pockets",
-                                &asset_server,
+                                asset_server,
                                 inventory,
                                 None,
                             );
@@ -183,7 +193,7 @@ pub fn spawn_inventory_ui(builder: &mut ChildBuilder, asset_server: &AssetServer
                 FlexDirection::Row,
                 builder,
                 "ressources",
-                &asset_server,
+                asset_server,
                 inventory,
                 Some((FlexDirection::Column, 9)),
             );
@@ -292,7 +302,7 @@ pub fn display_slots<const COUNT: usize>(
                                     builder,
                                     y * by_row_count + i,
                                     field,
-                                    &asset_server,
+                                    asset_server,
                                     inventory,
                                 ));
                             }
@@ -402,10 +412,10 @@ fn slot_interaction(
             Interaction::Pressed => {
                 let slot_type: SlotType = inv_slot.into();
 
-                let can_put_in_slot_type = !moving_stack_res
+                let can_put_in_slot_type = moving_stack_res
                     .0
                     .as_ref()
-                    .is_some_and(|stack| !stack.can_put_in_slot_type(slot_type));
+                    .is_none_or(|stack| stack.can_put_in_slot_type(slot_type));
 
                 if settings.keybinds.split_stack.pressed(&keyboard, &mouse) {
                     if let Some(moving_stack) = &mut moving_stack_res.0 {
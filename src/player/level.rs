@@ -0,0 +1,342 @@
+use super::{inventory::ui::InventoryUiState, mana::Mana, Player};
+use crate::{
+    gui::styles::text_style, lang::Lang, save::SaveWorldEvent, state::AppState, stats::Stats,
+    world::WorldEnterText,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub struct LevelPlugin;
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<XpEvent>().add_systems(
+            Update,
+            (
+                gain_xp,
+                (stat_button_interact, update_level_ui)
+                    .chain()
+                    .run_if(in_state(InventoryUiState::Opened)),
+            )
+                .run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+/// Experience earned by the player, sent when it kills a mob
+#[derive(Event)]
+pub struct XpEvent {
+    pub amount: u64,
+}
+
+/// Stats that can be raised with the points earned on level up
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stat {
+    Strength,
+    MaxHealth,
+    Def,
+    Speed,
+    Mana,
+}
+
+impl Stat {
+    pub const ALL: [Self; 5] = [
+        Self::Strength,
+        Self::MaxHealth,
+        Self::Def,
+        Self::Speed,
+        Self::Mana,
+    ];
+
+    /// Used by lang files
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Strength => "strength",
+            Self::MaxHealth => "max_health",
+            Self::Def => "def",
+            Self::Speed => "speed",
+            Self::Mana => "mana",
+        }
+    }
+
+    /// Gained for each point
+    pub fn step(&self) -> f32 {
+        match self {
+            Self::Strength => 1.,
+            Self::MaxHealth => 4.,
+            Self::Def => 0.5,
+            Self::Speed => 10.,
+            Self::Mana => 10.,
+        }
+    }
+
+    pub fn value(&self, stats: &Stats, mana: &Mana) -> f32 {
+        match self {
+            Self::Strength => stats.strength,
+            Self::MaxHealth => stats.max_health,
+            Self::Def => stats.def,
+            Self::Speed => stats.speed,
+            Self::Mana => mana.max(),
+        }
+    }
+}
+
+/// Points spent in each [`Stat`], the stats themselves are saved already raised
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct StatPoints {
+    pub strength: u32,
+    pub max_health: u32,
+    pub def: u32,
+    pub speed: u32,
+    pub mana: u32,
+}
+
+impl StatPoints {
+    fn get_mut(&mut self, stat: Stat) -> &mut u32 {
+        match stat {
+            Stat::Strength => &mut self.strength,
+            Stat::MaxHealth => &mut self.max_health,
+            Stat::Def => &mut self.def,
+            Stat::Speed => &mut self.speed,
+            Stat::Mana => &mut self.mana,
+        }
+    }
+}
+
+#[derive(Component, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PlayerLevel {
+    pub level: u32,
+    /// Earned since the last level up
+    pub xp: u64,
+    /// Earned but not spent yet
+    pub stat_points: u32,
+    pub spent: StatPoints,
+}
+
+impl Default for PlayerLevel {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            xp: 0,
+            stat_points: 0,
+            spent: StatPoints::default(),
+        }
+    }
+}
+
+impl PlayerLevel {
+    pub const MAX: u32 = 50;
+    const STAT_POINTS_PER_LEVEL: u32 = 3;
+
+    /// # Returns
+    /// The experience needed to go from `level` to the next one
+    pub fn xp_to_next(level: u32) -> u64 {
+        (20. * (level as f32).powf(1.5)).round() as u64
+    }
+
+    /// # Returns
+    /// How many levels were gained
+    pub fn add_xp(&mut self, amount: u64) -> u32 {
+        let start = self.level;
+        self.xp += amount;
+
+        while self.level < Self::MAX && self.xp >= Self::xp_to_next(self.level) {
+            self.xp -= Self::xp_to_next(self.level);
+            self.level += 1;
+            self.stat_points += Self::STAT_POINTS_PER_LEVEL;
+        }
+
+        self.level - start
+    }
+
+    pub fn mana_capacity(&self) -> f32 {
+        Mana::DEFAULT_MAX + self.spent.mana as f32 * Stat::Mana.step()
+    }
+
+    /// # Returns
+    /// [`true`] if a point was left to spend
+    pub fn spend(&mut self, stat: Stat, stats: &mut Stats, mana: &mut Mana) -> bool {
+        if self.stat_points == 0 {
+            return false;
+        }
+
+        self.stat_points -= 1;
+        *self.spent.get_mut(stat) += 1;
+
+        let step = stat.step();
+        match stat {
+            Stat::Strength => stats.strength += step,
+            Stat::MaxHealth => {
+                stats.max_health += step;
+                stats.health += step;
+            }
+            Stat::Def => stats.def += step,
+            Stat::Speed => stats.speed += step,
+            Stat::Mana => mana.set_max(self.mana_capacity()),
+        }
+
+        true
+    }
+}
+
+fn gain_xp(
+    mut commands: Commands,
+    mut xp_event: EventReader<XpEvent>,
+    mut query: Query<&mut PlayerLevel>,
+    mut save_event: EventWriter<SaveWorldEvent>,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+) {
+    let Ok(mut level) = query.get_single_mut() else {
+        return;
+    };
+
+    for event in xp_event.read() {
+        if level.add_xp(event.amount) == 0 {
+            continue;
+        }
+
+        info!("Player reached level {}", level.level);
+        WorldEnterText::spawn(
+            &mut commands,
+            &asset_server,
+            &format!("{} {}", lang.get("ui.level.level"), level.level),
+        );
+        save_event.send(SaveWorldEvent);
+    }
+}
+
+/// Shown next to the inventory
+#[derive(Component)]
+struct LevelText;
+
+#[derive(Component)]
+struct StatText(Stat);
+
+#[derive(Component)]
+struct StatButton(Stat);
+
+pub fn spawn_level_ui(
+    builder: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    lang: &Lang,
+    level: &PlayerLevel,
+    stats: &Stats,
+    mana: &Mana,
+) {
+    builder
+        .spawn(NodeBundle {
+            background_color: Color::DARK_GRAY.into(),
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                margin: UiRect::all(Val::Px(8.)),
+                padding: UiRect::all(Val::Px(8.)),
+                row_gap: Val::Px(4.),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|builder| {
+            builder.spawn((
+                LevelText,
+                TextBundle::from_section(level_text(lang, level), text_style(asset_server)),
+            ));
+
+            for stat in Stat::ALL {
+                builder
+                    .spawn(NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(12.),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|builder| {
+                        builder.spawn((
+                            StatText(stat),
+                            TextBundle::from_section(
+                                stat_text(lang, stat, stats, mana),
+                                text_style(asset_server),
+                            ),
+                        ));
+
+                        builder
+                            .spawn((
+                                StatButton(stat),
+                                ButtonBundle {
+                                    background_color: Color::GRAY.into(),
+                                    style: Style {
+                                        width: Val::Px(24.),
+                                        height: Val::Px(24.),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    ..Default::default()
+                                },
+                            ))
+                            .with_children(|builder| {
+                                builder
+                                    .spawn(TextBundle::from_section("+", text_style(asset_server)));
+                            });
+                    });
+            }
+        });
+}
+
+fn level_text(lang: &Lang, level: &PlayerLevel) -> String {
+    format!(
+        "{} {} - {}/{} xp - {} : {}",
+        lang.get("ui.level.level"),
+        level.level,
+        level.xp,
+        PlayerLevel::xp_to_next(level.level),
+        lang.get("ui.level.stat_points"),
+        level.stat_points
+    )
+}
+
+fn stat_text(lang: &Lang, stat: Stat, stats: &Stats, mana: &Mana) -> String {
+    format!(
+        "{} : {}",
+        lang.get(&format!("ui.level.stats.{}", stat.name())),
+        stat.value(stats, mana)
+    )
+}
+
+fn stat_button_interact(
+    query: Query<(&Interaction, &StatButton), Changed<Interaction>>,
+    mut player_query: Query<(&mut PlayerLevel, &mut Stats, &mut Player)>,
+) {
+    let Ok((mut level, mut stats, mut player)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for (interaction, button) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            level.spend(button.0, &mut stats, &mut player.mana);
+        }
+    }
+}
+
+fn update_level_ui(
+    player_query: Query<(&PlayerLevel, &Stats, &Player), Changed<PlayerLevel>>,
+    mut level_text_query: Query<&mut Text, With<LevelText>>,
+    mut stat_text_query: Query<(&mut Text, &StatText), Without<LevelText>>,
+    lang: Res<Lang>,
+) {
+    let Ok((level, stats, player)) = player_query.get_single() else {
+        return;
+    };
+
+    if let Ok(mut text) = level_text_query.get_single_mut() {
+        text.sections[0].value = level_text(&lang, level);
+    }
+
+    for (mut text, StatText(stat)) in stat_text_query.iter_mut() {
+        text.sections[0].value = stat_text(&lang, *stat, stats, &player.mana);
+    }
+}
@@ -3,19 +3,35 @@ use std::ops::{AddAssign, SubAssign};
 use bevy::time::Time;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
 pub struct Mana {
     value: f32,
     regen_rate: f32,
+    /// Grows with the stat points of the [`PlayerLevel`](super::level::PlayerLevel), which sets it back after loading
+    #[serde(skip, default = "Mana::default_max")]
+    max: f32,
 }
 
 impl Mana {
-    pub const MAX: f32 = 100.;
+    pub const DEFAULT_MAX: f32 = 100.;
+
+    fn default_max() -> f32 {
+        Self::DEFAULT_MAX
+    }
 
     pub fn get(&self) -> f32 {
         self.value
     }
 
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    pub fn set_max(&mut self, max: f32) {
+        self.max = max;
+        self.value = self.value.min(max);
+    }
+
     /// # Returns
     /// [`true`] if removing was successful
     pub fn try_remove(&mut self, amount: f32) -> bool {
@@ -31,7 +47,7 @@ impl Mana {
     }
 
     pub fn tick(&mut self, time: &Time) {
-        if self.value < Self::MAX {
+        if self.value < self.max {
             self.value += time.delta_seconds() * self.regen_rate
         }
     }
@@ -40,8 +56,9 @@ impl Mana {
 impl Default for Mana {
     fn default() -> Self {
         Self {
-            value: Self::MAX,
+            value: Self::DEFAULT_MAX,
             regen_rate: 0.5,
+            max: Self::DEFAULT_MAX,
         }
     }
 }
@@ -49,8 +66,8 @@ impl Default for Mana {
 impl AddAssign<f32> for Mana {
     fn add_assign(&mut self, rhs: f32) {
         self.value += rhs;
-        if self.value > Self::MAX {
-            self.value = Self::MAX
+        if self.value > self.max {
+            self.value = self.max
         }
     }
 }
@@ -1,5 +1,7 @@
 pub mod class;
+pub mod death;
 pub mod inventory;
+pub mod level;
 pub mod mana;
 pub mod money;
 
@@ -7,25 +9,30 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use self::class::{PlayerClass, PlayerClasses, PlayerClassesPlugin};
+use self::death::{is_alive, DeathPlugin};
 use self::inventory::{Inventory, InventoryPlugin};
+use self::level::{LevelPlugin, PlayerLevel};
 use self::mana::Mana;
 use self::money::{Money, MoneyPlugin};
 use crate::animation::{
     AnimatedSpriteBundle, Animation, AnimationController, AnimationDirection, AnimationMode,
 };
 use crate::animation_maker;
-use crate::effects::{Effect, EffectsController, EffectsPlugin};
+use crate::damage::DamageEvent;
+use crate::effects::{EffectsController, EffectsPlugin};
 use crate::gui::{
     misc::ease_out_quad,
     settings::{fov::FOV_MULTIPLIER, range::RangeSetting, Settings},
 };
 use crate::items::item::ItemPlugin;
 use crate::lang::Lang;
-use crate::mob::Mob;
+use crate::mob::{definition::MobDefinitions, Mob};
 use crate::npc::dialog::in_dialog;
+use crate::random::{load_world_rng, WorldRng};
 use crate::save::SaveData;
 use crate::state::AppState;
 use crate::stats::Stats;
+use crate::weather::WorldWeather;
 use crate::world::{is_loading, BLOCK_SIZE};
 use bevy::sprite::Anchor;
 use bevy::{prelude::*, utils::HashMap};
@@ -34,7 +41,7 @@ use serde::{Deserialize, Serialize};
 
 pub const PLAYER_SPRITE_SHEETS_X_SIZE: u32 = 128;
 
-#[derive(Component, Serialize, Deserialize, Clone)]
+#[derive(Component, Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct Player {
     pub class: PlayerClasses,
     pub inventory: Inventory,
@@ -51,7 +58,7 @@ impl Player {
     pub const SIZE: f32 = 96.0;
 }
 
-#[derive(Component, Clone)]
+#[derive(Component, Clone, PartialEq, Debug)]
 pub struct ChainAttack {
     end_at: u8,
     pub timer: Timer,
@@ -114,8 +121,9 @@ impl Plugin for PlayerPlugin {
         app.add_systems(
             Update,
             (
-                character_controller_update.run_if(not(is_loading).and_then(not(in_dialog))),
-                player_setup,
+                character_controller_update
+                    .run_if(not(is_loading).and_then(not(in_dialog)).and_then(is_alive)),
+                player_setup.after(load_world_rng),
             )
                 .run_if(in_state(AppState::InGame)),
         )
@@ -132,6 +140,8 @@ impl Plugin for PlayerPlugin {
             PlayerClassesPlugin,
             MoneyPlugin,
             EffectsPlugin,
+            DeathPlugin,
+            LevelPlugin,
         ));
     }
 }
@@ -147,6 +157,7 @@ pub struct PlayerBundle {
     collider: Collider,
     stats: Stats,
     effects_controller: EffectsController,
+    level: PlayerLevel,
 }
 
 fn player_setup(
@@ -155,12 +166,23 @@ fn player_setup(
     lang: Res<Lang>,
     asset_server: Res<AssetServer>,
     camera_query: Query<Entity, With<Camera>>,
+    definitions: Res<MobDefinitions>,
+    mut rng: ResMut<WorldRng>,
 ) {
     for ev in event.read() {
         let save = ev.read();
 
         let world = save.current_world.clone();
-        world.spawn(&mut commands, &asset_server, &lang, camera_query.single());
+        let world_save = save.worlds.get(&world);
+        world.spawn(
+            &mut commands,
+            &asset_server,
+            &lang,
+            camera_query.single(),
+            world_save,
+            &definitions,
+            &mut rng,
+        );
 
         let controller: KinematicCharacterController = KinematicCharacterController {
             autostep: Some(CharacterAutostep {
@@ -172,8 +194,8 @@ fn player_setup(
         };
 
         let mut player = save.player.player.clone();
-        let mut transform = Transform::from_translation(save.player.pos.extend(Player::EXTEND));
-        transform.translation.y += BLOCK_SIZE;
+        player.mana.set_max(save.player.level.mana_capacity());
+        let transform = Transform::from_translation(save.player.pos.extend(Player::EXTEND));
 
         let get_texture_path = |name: &str| -> PathBuf { player.class.get_texture_path(name) };
 
@@ -216,6 +238,7 @@ fn player_setup(
                     sprite: TextureAtlasSprite {
                         anchor: Player::SPRITE_ANCHOR,
                         custom_size: Some(Vec2::splat(Player::SIZE)),
+                        flip_x: save.player.facing_left,
                         ..Default::default()
                     },
                     ..Default::default()
@@ -226,8 +249,9 @@ fn player_setup(
             collider: Collider::capsule_y(13.5, 13.5),
             controller,
             rigid_body: RigidBody::KinematicPositionBased,
-            stats: Stats::default().with_health(20.0),
+            stats: save.player.stats.clone(),
             effects_controller: save.player.effects.clone(),
+            level: save.player.level.clone(),
         });
     }
 }
@@ -253,11 +277,18 @@ pub fn character_controller_update(
         &mut Player,
         &EffectsController,
     )>,
-    mut mob_query: Query<(&mut Stats, &mut Mob), Without<Player>>,
+    mob_query: Query<(), With<Mob>>,
+    mut damage_event: EventWriter<DamageEvent>,
     rapier_context: Res<RapierContext>,
     mut camera_query: Query<&mut Transform, (With<Camera2d>, Without<Player>)>,
+    weather_query: Query<&WorldWeather>,
     settings: Res<Settings>,
 ) {
+    let speed_multiplier = weather_query
+        .get_single()
+        .map(|weather| weather.current.speed_multiplier())
+        .unwrap_or(1.);
+
     for (
         entity,
         mut sprite,
@@ -296,8 +327,8 @@ pub fn character_controller_update(
             direction.y -= 1.0;
         }
 
-        if let Some(data) = effects.get_effect(&Effect::Levitation) {
-            direction.y = data.level as f32 + 1.;
+        if let Some(lift) = effects.levitation() {
+            direction.y = lift;
         }
 
         //Prevent from dropping in the void
@@ -313,10 +344,12 @@ pub fn character_controller_update(
             player.jump_timer.tick(time.delta());
 
             //Jump impulsion
-            direction.y += 2.5 * ease_out_quad(player.jump_timer.percent());
+            direction.y +=
+                2.5 * effects.jump_multiplier() * ease_out_quad(player.jump_timer.percent());
         }
 
-        direction.x *= stats.speed * time.delta_seconds();
+        direction.x *=
+            stats.speed * speed_multiplier * effects.speed_multiplier() * time.delta_seconds();
         direction.y *= stats.mass * time.delta_seconds();
 
         controller.translation = Some(direction);
@@ -370,9 +403,8 @@ pub fn character_controller_update(
                     hitbox_translation,
                     &rapier_context,
                     |hit_entity| {
-                        if let Ok((mut stats, mut mob)) = mob_query.get_mut(hit_entity) {
-                            stats.take_damage(3.);
-                            mob.hit_animation();
+                        if mob_query.contains(hit_entity) {
+                            damage_event.send(DamageEvent::new(Some(entity), hit_entity, 3.));
                         }
                         true
                     },
@@ -7,7 +7,7 @@ use crate::{stats::Stats, world::BLOCK_SIZE};
 
 use super::Player;
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
 pub struct Money {
     amount: u64,
 }
@@ -2,10 +2,12 @@ use bevy::app::Plugin;
 use bevy_rapier2d::plugin::{NoUserData, RapierPhysicsPlugin};
 
 use crate::{
-    animation::AnimationPlugin, background::ParallaxBackgroundPlugin, chest::ChestPlugin,
-    commands::CommandsPlugin, gui::GuiPlugin, interactable::InteractionPlugin, mob::MobPlugin,
-    music::MusicPlugin, npc::NpcPlugin, ore::OrePlugin, player::PlayerPlugin, save::SavePlugin,
-    state::AppStatePlugin, stats::StatsPlugin, tiled, world::WorldPlugin,
+    animation::AnimationPlugin, background::ParallaxBackgroundPlugin, boss::BossPlugin,
+    chest::ChestPlugin, commands::CommandsPlugin, damage::DamagePlugin, day_night::DayNightPlugin,
+    dungeon::DungeonPlugin, gui::GuiPlugin, interactable::InteractionPlugin, mob::MobPlugin,
+    music::MusicPlugin, npc::NpcPlugin, ore::OrePlugin, player::PlayerPlugin, portal::PortalPlugin,
+    projectile::ProjectilePlugin, random::RandomPlugin, save::SavePlugin, state::AppStatePlugin,
+    stats::StatsPlugin, tiled, weather::WeatherPlugin, world::WorldPlugin,
 };
 
 pub struct TerradventurePlugin;
@@ -29,6 +31,16 @@ impl Plugin for TerradventurePlugin {
                 ParallaxBackgroundPlugin,
                 NpcPlugin,
                 OrePlugin,
+            ))
+            .add_plugins((
+                PortalPlugin,
+                DayNightPlugin,
+                WeatherPlugin,
+                DungeonPlugin,
+                RandomPlugin,
+                BossPlugin,
+                ProjectilePlugin,
+                DamagePlugin,
             ));
     }
 }
@@ -0,0 +1,174 @@
+use crate::{
+    boss::is_arena_locked,
+    interactable::Interactable,
+    lang::Lang,
+    mob::definition::MobDefinitions,
+    player::{level::PlayerLevel, Player},
+    random::WorldRng,
+    save::{CurrentSave, PlayerSave, WorldSnapshot},
+    state::AppState,
+    tiled::Loaded,
+    world::{World, WorldEnterText},
+};
+use bevy::prelude::*;
+
+/// Moves the player to another [`World`], spawned from the "Portal" and "Door" objects of a Tiled map
+#[derive(Component)]
+pub struct Portal {
+    pub target: World,
+    /// Name of the [`SpawnPoint`] of the target world where the player appears
+    pub spawn_point: String,
+}
+
+/// Where a player coming from a [`Portal`] appears, spawned from the "Spawn" objects of a Tiled map
+#[derive(Component)]
+pub struct SpawnPoint {
+    pub name: String,
+}
+
+#[derive(Event)]
+pub struct TravelEvent {
+    pub target: World,
+    pub spawn_point: String,
+}
+
+/// Spawn point the player is waiting for while the target world loads
+#[derive(Resource, Default)]
+pub struct PendingSpawnPoint(pub Option<String>);
+
+pub struct PortalPlugin;
+impl Plugin for PortalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TravelEvent>()
+            .init_resource::<PendingSpawnPoint>()
+            .add_systems(
+                Update,
+                (
+                    portal_update.run_if(not(is_arena_locked)),
+                    travel,
+                    place_player,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+/// Whether the player is waiting for a world to load before being moved to its [`SpawnPoint`]
+pub fn is_travelling(pending_spawn_point: Res<PendingSpawnPoint>) -> bool {
+    pending_spawn_point.0.is_some()
+}
+
+fn portal_update(
+    mut commands: Commands,
+    query: Query<(&Portal, &Interactable)>,
+    player_query: Query<&PlayerLevel>,
+    mut travel_event: EventWriter<TravelEvent>,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+) {
+    let level = player_query.get_single().map_or(1, |level| level.level);
+
+    for (portal, interactable) in query.iter() {
+        if !interactable.just_pressed() {
+            continue;
+        }
+
+        let required_level = portal.target.required_level();
+        if level < required_level {
+            WorldEnterText::spawn(
+                &mut commands,
+                &asset_server,
+                &format!("{} {required_level}", lang.get("ui.level.required")),
+            );
+            continue;
+        }
+
+        travel_event.send(TravelEvent {
+            target: portal.target.clone(),
+            spawn_point: portal.spawn_point.clone(),
+        });
+    }
+}
+
+fn travel(
+    mut commands: Commands,
+    mut travel_event: EventReader<TravelEvent>,
+    snapshot: WorldSnapshot,
+    mut current_save: ResMut<CurrentSave>,
+    mut pending_spawn_point: ResMut<PendingSpawnPoint>,
+    camera_query: Query<Entity, With<Camera>>,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+    definitions: Res<MobDefinitions>,
+    mut rng: ResMut<WorldRng>,
+) {
+    let Some(event) = travel_event.read().last() else {
+        return;
+    };
+
+    let Some(save_data) = &mut current_save.0 else {
+        return;
+    };
+
+    // Keep the state of the world we leave so it's restored when coming back
+    let Some(mut save) = snapshot.save(&save_data.data) else {
+        return;
+    };
+    snapshot.despawn(&mut commands);
+
+    info!(
+        "Travelling to {}.{}",
+        event.target.get_type(),
+        event.target.name()
+    );
+
+    // Every run through a generated dungeon has a new layout, respawning inside keeps it
+    if event.target.dungeon_templates().is_some() && event.target != save.current_world {
+        save.worlds.remove(&event.target);
+    }
+
+    save.current_world = event.target.clone();
+    event.target.clone().spawn(
+        &mut commands,
+        &asset_server,
+        &lang,
+        camera_query.single(),
+        save.worlds.get(&event.target),
+        &definitions,
+        &mut rng,
+    );
+
+    save_data.data = save;
+    pending_spawn_point.0 = Some(event.spawn_point.clone());
+}
+
+pub fn place_player(
+    mut pending_spawn_point: ResMut<PendingSpawnPoint>,
+    world_query: Query<(), (With<World>, Added<Loaded>)>,
+    spawn_points: Query<(&SpawnPoint, &Transform), Without<Player>>,
+    mut player_query: Query<&mut Transform, With<Player>>,
+) {
+    if world_query.is_empty() {
+        return;
+    }
+
+    let Some(name) = pending_spawn_point.0.take() else {
+        return;
+    };
+
+    let Ok(mut player_transform) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let pos = spawn_points
+        .iter()
+        .find(|(spawn_point, _)| spawn_point.name == name)
+        .map(|(_, transform)| transform.translation.xy())
+        .unwrap_or_else(|| {
+            warn!("Spawn point {name} not found");
+            PlayerSave::default().pos
+        });
+
+    player_transform.translation = pos.extend(player_transform.translation.z);
+}
@@ -0,0 +1,221 @@
+use crate::{
+    animation::{AnimatedSpriteBundle, AnimationController},
+    damage::{DamageEvent, DamageType},
+    effects::{Effect, EffectData},
+    mob::Mob,
+    player::Player,
+    state::AppState,
+    stats::Stats,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+pub struct ProjectilePlugin;
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            update_projectiles.run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+/// Side of the shooter, a projectile only hurts the other side
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Faction {
+    Player,
+    Mob,
+}
+
+#[derive(Component)]
+pub struct Projectile {
+    /// Pixels per second
+    pub velocity: Vec2,
+    /// Pixels per second squared pulling the projectile down, 0 flies straight
+    pub gravity: f32,
+    pub lifetime: Timer,
+    /// Targets the projectile goes through before stopping, 0 stops at the first one
+    pub pierce: u32,
+    pub damage: f32,
+    pub damage_type: DamageType,
+    /// Given to each target hit
+    pub effect: Option<(Effect, EffectData)>,
+    pub faction: Faction,
+    /// Never hit, the shooter usually
+    pub owner: Option<Entity>,
+    /// Each target is only hit once by a piercing projectile
+    hit_entities: Vec<Entity>,
+    /// Whether the projectile stopped and plays the rest of its animation before disappearing
+    stopped: bool,
+}
+
+impl Projectile {
+    pub const DEFAULT_LIFETIME: f32 = 10.;
+
+    pub fn new(velocity: Vec2, damage: f32, faction: Faction) -> Self {
+        Self {
+            velocity,
+            gravity: 0.,
+            lifetime: Timer::from_seconds(Self::DEFAULT_LIFETIME, TimerMode::Once),
+            pierce: 0,
+            damage,
+            damage_type: DamageType::default(),
+            effect: None,
+            faction,
+            owner: None,
+            hit_entities: Vec::new(),
+            stopped: false,
+        }
+    }
+
+    pub fn with_gravity(mut self, gravity: f32) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn with_lifetime(mut self, seconds: f32) -> Self {
+        self.lifetime = Timer::from_seconds(seconds, TimerMode::Once);
+        self
+    }
+
+    pub fn with_pierce(mut self, pierce: u32) -> Self {
+        self.pierce = pierce;
+        self
+    }
+
+    pub fn with_damage_type(mut self, damage_type: DamageType) -> Self {
+        self.damage_type = damage_type;
+        self
+    }
+
+    pub fn with_effect(mut self, effect: Effect, seconds: f32, level: u8) -> Self {
+        self.effect = Some((effect, EffectData::new(seconds, level)));
+        self
+    }
+
+    pub fn with_owner(mut self, owner: Entity) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// # Returns
+    /// The velocity to reach a target `offset` away, aiming higher to make up for the gravity
+    pub fn aim(offset: Vec2, speed: f32, gravity: f32) -> Vec2 {
+        let direction = offset.normalize_or_zero();
+        let flight_time = offset.length() / speed;
+        direction * speed + Vec2::new(0., gravity * flight_time / 2.)
+    }
+
+    /// # Returns
+    /// Whether the projectile hurts this entity
+    fn targets(&self, is_mob: bool, is_player: bool) -> bool {
+        match self.faction {
+            Faction::Player => is_mob,
+            Faction::Mob => is_player,
+        }
+    }
+}
+
+/// The animation of a projectile is paused on its first frame while flying and played when it hits
+#[derive(Bundle)]
+pub struct ProjectileBundle {
+    pub projectile: Projectile,
+    pub collider: Collider,
+    /// Projectiles don't block anything, they look for what they hit themselves
+    pub sensor: Sensor,
+    pub sprite: AnimatedSpriteBundle,
+}
+
+fn update_projectiles(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    mut query: Query<(
+        Entity,
+        &mut Projectile,
+        &mut Transform,
+        &Collider,
+        &mut AnimationController,
+        &TextureAtlasSprite,
+    )>,
+    target_query: Query<(Has<Mob>, Has<Player>), With<Stats>>,
+    mut damage_event: EventWriter<DamageEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut projectile, mut transform, collider, mut animation_controller, sprite) in
+        query.iter_mut()
+    {
+        if projectile.stopped {
+            animation_controller.tick(&time);
+            if animation_controller.just_finished.is_some() {
+                commands.entity(entity).despawn();
+            }
+            continue;
+        }
+
+        projectile.lifetime.tick(time.delta());
+        if projectile.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let delta = time.delta_seconds();
+        projectile.velocity.y -= projectile.gravity * delta;
+        transform.translation += (projectile.velocity * delta).extend(0.);
+
+        // Straight projectiles keep their orientation, falling ones follow their curve
+        if projectile.gravity != 0. {
+            let heading = if sprite.flip_x {
+                -projectile.velocity
+            } else {
+                projectile.velocity
+            };
+            transform.rotation = Quat::from_rotation_z(heading.y.atan2(heading.x));
+        }
+
+        let owner = projectile.owner;
+        let predicate = |e: Entity| Some(e) != owner;
+        let mut hits = Vec::new();
+        rapier_context.intersections_with_shape(
+            transform.translation.xy(),
+            transform.rotation.to_euler(EulerRot::XYZ).2,
+            collider,
+            QueryFilter {
+                predicate: Some(&predicate),
+                ..QueryFilter::default().exclude_sensors()
+            },
+            |hit_entity| {
+                hits.push(hit_entity);
+                true
+            },
+        );
+
+        for hit_entity in hits {
+            if projectile.hit_entities.contains(&hit_entity) {
+                continue;
+            }
+
+            let Ok((is_mob, is_player)) = target_query.get(hit_entity) else {
+                // The terrain
+                projectile.stopped = true;
+                break;
+            };
+
+            // Goes through the allies of the shooter
+            if !projectile.targets(is_mob, is_player) {
+                continue;
+            }
+
+            damage_event.send(DamageEvent {
+                effect: projectile.effect.clone(),
+                ..DamageEvent::new(projectile.owner, hit_entity, projectile.damage)
+                    .with_type(projectile.damage_type)
+            });
+
+            projectile.hit_entities.push(hit_entity);
+            if projectile.hit_entities.len() > projectile.pierce as usize {
+                projectile.stopped = true;
+                break;
+            }
+        }
+    }
+}
@@ -1,6 +1,96 @@
-use rand::{prelude::SliceRandom, thread_rng};
+use crate::save::SaveData;
+use bevy::prelude::*;
+use rand::{prelude::SliceRandom, rngs::StdRng, thread_rng, Rng, RngCore, SeedableRng};
 use serde::Deserialize;
 
+pub struct RandomPlugin;
+impl Plugin for RandomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldRng>()
+            .add_systems(Update, load_world_rng);
+    }
+}
+
+/// Generator behind everything random that changes the game, seeded from [`Save::seed`](crate::save::Save::seed)
+/// and restarted from [`Save::rng_state`](crate::save::Save::rng_state) so a reloaded world goes on
+/// where it was saved instead of replaying the same outcomes
+///
+/// Purely cosmetic effects keep using [`thread_rng`] so they don't shift the sequence
+#[derive(Resource)]
+pub struct WorldRng(StdRng);
+
+impl WorldRng {
+    pub fn new(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+
+    /// Restarts the generator from a seed drawn from it, so playing on and reloading the save give the same outcomes
+    /// # Returns
+    /// The new seed, kept in [`Save::rng_state`](crate::save::Save::rng_state)
+    pub fn reseed(&mut self) -> u64 {
+        let seed = self.0.gen();
+        *self = Self::new(seed);
+        seed
+    }
+
+    /// # Returns
+    /// A new random world seed
+    pub fn random_seed() -> u64 {
+        thread_rng().gen()
+    }
+
+    /// Seed typed by the player, a number is used as is and any other text is hashed,
+    /// an empty text gives a random seed
+    pub fn seed_from_text(text: &str) -> u64 {
+        let text = text.trim();
+        if text.is_empty() {
+            return Self::random_seed();
+        }
+
+        text.parse()
+            .unwrap_or_else(|_| Self::seed_from_bytes(text.as_bytes()))
+    }
+
+    /// Seed always given by the same bytes
+    pub fn seed_from_bytes(bytes: &[u8]) -> u64 {
+        // FNV-1a, unlike the std hasher it's guaranteed to stay the same between builds
+        bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+}
+
+impl Default for WorldRng {
+    fn default() -> Self {
+        Self::new(Self::random_seed())
+    }
+}
+
+impl RngCore for WorldRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+/// Restarts the generator from the state of the loaded world, must run before the world is spawned
+pub fn load_world_rng(mut event: EventReader<SaveData>, mut rng: ResMut<WorldRng>) {
+    for ev in event.read() {
+        *rng = WorldRng::new(ev.read().rng_state);
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct RandomWeightedTable<T>
 where
@@ -46,11 +136,20 @@ where
         }
     }
 
-    pub fn get_random(&self) -> Vec<T> {
+    pub fn get_random<R: Rng>(&self, rng: &mut R) -> Vec<T> {
         self.rates
-            .choose_multiple_weighted(&mut thread_rng(), self.rolls, |item| item.weight)
+            .choose_multiple_weighted(rng, self.rolls, |item| item.weight)
             .unwrap()
             .map(|item| item.data.clone())
             .collect::<Vec<_>>()
     }
 }
+
+impl<T> Extend<RandomWeightedRate<T>> for RandomWeightedTable<T>
+where
+    T: Clone,
+{
+    fn extend<I: IntoIterator<Item = RandomWeightedRate<T>>>(&mut self, iter: I) {
+        self.rates.extend(iter);
+    }
+}
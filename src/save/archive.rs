@@ -0,0 +1,105 @@
+use super::{write_atomic, Save, SaveError, SaveMetaData, SAVES_DIR};
+use crate::CONFIG_DIR;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A whole save folder packed into a single file to share it
+#[derive(Serialize, Deserialize)]
+struct WorldArchive {
+    meta: SaveMetaData,
+    /// Content of the world file as written on disk, in any save version
+    world: Vec<u8>,
+}
+
+pub static EXPORT_DIR: Lazy<PathBuf> = Lazy::new(|| CONFIG_DIR.join("exports"));
+pub static IMPORT_DIR: Lazy<PathBuf> = Lazy::new(|| CONFIG_DIR.join("imports"));
+
+impl Save {
+    pub const ARCHIVE_EXTENSION: &'static str = "tdworld";
+
+    pub fn export(name: &str, archive_path: &Path) -> Result<(), SaveError> {
+        let path = SAVES_DIR.join(name);
+        let archive = WorldArchive {
+            meta: SaveMetaData::from_save_path(&path)?,
+            world: fs::read(path.join(Self::FILE_NAME))?,
+        };
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        bincode::serialize_into(&mut encoder, &archive)?;
+        write_atomic(archive_path, &encoder.finish()?)?;
+        Ok(())
+    }
+
+    /// Exports the save into [`EXPORT_DIR`]
+    /// # Returns
+    /// The path of the written archive
+    pub fn export_default(name: &str) -> Result<PathBuf, SaveError> {
+        fs::create_dir_all(&*EXPORT_DIR)?;
+        let archive_path = EXPORT_DIR.join(format!("{name}.{}", Self::ARCHIVE_EXTENSION));
+        Self::export(name, &archive_path)?;
+        Ok(archive_path)
+    }
+
+    /// Unpacks an archive into a new save folder, renaming it if a save with the same name already exists
+    /// # Returns
+    /// The name of the imported save
+    pub fn import(archive_path: &Path) -> Result<String, SaveError> {
+        let decoder = GzDecoder::new(fs::File::open(archive_path)?);
+        let mut archive: WorldArchive = bincode::deserialize_from(decoder)?;
+
+        if !Self::is_valid_name(&archive.meta.name) {
+            return Err(SaveError::InvalidArchive("invalid world name"));
+        }
+
+        // Refuse payloads the game couldn't load anyway
+        Self::decode(&archive.world)?;
+
+        let name = Self::available_name(&archive.meta.name);
+        let path = SAVES_DIR.join(&name);
+        fs::create_dir_all(&path)?;
+
+        archive.meta.name = name.clone();
+        archive.meta.save(&path)?;
+        write_atomic(&path.join(Self::FILE_NAME), &archive.world)?;
+
+        Ok(name)
+    }
+
+    /// Imports every archive found in [`IMPORT_DIR`], the imported archives are removed
+    pub fn import_all() -> Vec<Result<String, SaveError>> {
+        let Ok(dir) = IMPORT_DIR.read_dir() else {
+            let _ = fs::create_dir_all(&*IMPORT_DIR);
+            return Vec::new();
+        };
+
+        dir.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == Self::ARCHIVE_EXTENSION)
+            })
+            .map(|path| {
+                let name = Self::import(&path)?;
+                fs::remove_file(path)?;
+                Ok(name)
+            })
+            .collect()
+    }
+
+    /// # Returns
+    /// `name` if no save uses it yet, else `name (n)` with the first free n
+    pub fn available_name(name: &str) -> String {
+        if !SAVES_DIR.join(name).exists() {
+            return name.to_owned();
+        }
+
+        (2..)
+            .map(|i| format!("{name} ({i})"))
+            .find(|candidate| !SAVES_DIR.join(candidate).exists())
+            .unwrap()
+    }
+}
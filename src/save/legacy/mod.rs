@@ -0,0 +1,16 @@
+//! Frozen layouts of older save versions, each module knows how to upgrade itself to the next version
+//!
+//! They only use the copies of [`types`], so changing a game type never changes how an old save is read
+
+pub mod types;
+pub mod v1;
+pub mod v10;
+pub mod v11;
+pub mod v2;
+pub mod v3;
+pub mod v4;
+pub mod v5;
+pub mod v6;
+pub mod v7;
+pub mod v8;
+pub mod v9;
@@ -0,0 +1,246 @@
+//! Frozen copies of the game types written in the saves up to version 11
+//!
+//! Never edit them, a game type changing its layout needs a new save version and keeps its old shape here
+
+use bevy::{prelude::Vec2, utils::hashbrown::HashMap};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize)]
+pub struct Player {
+    pub class: PlayerClasses,
+    pub inventory: Inventory,
+    pub jump_timer: Timer,
+    pub money: Money,
+    pub mana: Mana,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum PlayerClasses {
+    Archer,
+    Enchantress,
+    Knight,
+    Musketeer,
+    Swordsman,
+    Wizard,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Inventory {
+    pub ressources: [Slot; 27],
+    pub armor: [Slot; 4],
+    pub pockets: [Slot; 2],
+    pub accessories: [Slot; 2],
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Slot {
+    pub item: Option<ItemStack>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ItemStack {
+    pub item: Item,
+    pub count: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum Item {
+    ManaPotion,
+    LevitationPotion,
+    Porkchop,
+    UnprocessedOre(Ore),
+    ProcessedOre(Ore),
+    SwiftnessPotion,
+    StrengthPotion,
+    RegenerationPotion,
+    ResistancePotion,
+    JumpPotion,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum Ore {
+    Amethyst,
+    Copper,
+    Diamond,
+    Emerald,
+    Gold,
+    Ruby,
+    Silver,
+    WitheDiamond,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Money {
+    pub amount: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Mana {
+    pub value: f32,
+    pub regen_rate: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Timer {
+    pub stopwatch: Stopwatch,
+    pub duration: Duration,
+    pub mode: TimerMode,
+    pub finished: bool,
+    pub times_finished_this_tick: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Stopwatch {
+    pub elapsed: Duration,
+    pub paused: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum TimerMode {
+    Once,
+    Repeating,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Stats {
+    pub strength: f32,
+    pub regen_rate: f32,
+    pub health: f32,
+    pub max_health: f32,
+    pub def: f32,
+    pub speed: f32,
+    pub mass: f32,
+    pub resistances: Resistances,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Resistances {
+    pub physical: f32,
+    pub fire: f32,
+    pub poison: f32,
+    pub magic: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EffectsController {
+    pub effects: HashMap<Effect, EffectData>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Effect {
+    Levitation,
+    Burning,
+    Poisoned,
+    Slowed,
+    Swiftness,
+    Strength,
+    Regeneration,
+    Resistance,
+    JumpBoost,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EffectData {
+    pub timer: Timer,
+    pub level: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlayerLevel {
+    pub level: u32,
+    pub xp: u64,
+    pub stat_points: u32,
+    pub spent: StatPoints,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct StatPoints {
+    pub strength: u32,
+    pub max_health: u32,
+    pub def: u32,
+    pub speed: u32,
+    pub mana: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum MobObject {
+    Rabbit,
+    Pig,
+    Defined(DefinedMob),
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DefinedMob {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum World {
+    Biome(Biome),
+    Dungeon(Dungeon),
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Biome {
+    Plains,
+    Forest,
+    Desert,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Dungeon {
+    Pyramid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorldClock {
+    pub day: u32,
+    pub time: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorldWeather {
+    pub seed: u64,
+    pub period: u64,
+    pub current: Weather,
+    pub remaining: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum Weather {
+    Clear,
+    Rain,
+    Sandstorm,
+    Fog,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ItemSave {
+    pub stack: ItemStack,
+    pub pos: Vec2,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum MapObjectSave {
+    Ore {
+        current_ore: Ore,
+        regrow_at: Option<DateTime<Local>>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CheckpointSave {
+    pub world: World,
+    pub spawn_point: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeathSave {
+    pub world: World,
+    pub pos: Vec2,
+    pub date: DateTime<Local>,
+    pub money_lost: u64,
+    pub items_dropped: u32,
+}
@@ -0,0 +1,43 @@
+//! Version 1, before the player's facing direction was saved
+
+use super::{
+    types::{EffectsController, Player, World},
+    v10::Stats,
+    v2 as next,
+};
+use crate::save::SaveError;
+use bevy::{prelude::*, utils::hashbrown::HashMap};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, next::WorldSave>,
+    pub current_world: World,
+}
+
+#[derive(Deserialize)]
+pub struct PlayerSave {
+    pub player: Player,
+    pub stats: Stats,
+    pub pos: Vec2,
+    pub effects: EffectsController,
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = next::Save {
+        player: next::PlayerSave {
+            player: save.player.player,
+            stats: save.player.stats,
+            pos: save.player.pos,
+            effects: save.player.effects,
+            facing_left: false,
+        },
+        worlds: save.worlds,
+        current_world: save.current_world,
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
@@ -0,0 +1,121 @@
+//! Version 10, before stats had resistances
+
+use super::types::{
+    EffectsController, MobObject, Player, PlayerLevel, Resistances, World, WorldClock, WorldWeather,
+};
+use super::v11 as next;
+pub use super::v11::{CheckpointSave, DeathSave, ItemSave, MapObjectSave};
+use crate::save::SaveError;
+use bevy::{prelude::*, utils::hashbrown::HashMap};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+    pub clock: WorldClock,
+    pub seed: u64,
+    pub defeated_bosses: Vec<String>,
+    pub checkpoint: Option<CheckpointSave>,
+    pub deaths: Vec<DeathSave>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlayerSave {
+    pub player: Player,
+    pub stats: Stats,
+    pub pos: Vec2,
+    pub effects: EffectsController,
+    pub facing_left: bool,
+    pub level: PlayerLevel,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorldSave {
+    pub mobs: Vec<MobSave>,
+    pub items: Vec<ItemSave>,
+    pub available_chests: Option<Vec<String>>,
+    pub objects: HashMap<u32, MapObjectSave>,
+    pub weather: Option<WorldWeather>,
+    pub dungeon_seed: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MobSave {
+    pub data: MobObject,
+    pub stats: Stats,
+    pub pos: Vec2,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Stats {
+    pub strength: f32,
+    pub regen_rate: f32,
+    pub health: f32,
+    pub max_health: f32,
+    pub def: f32,
+    pub speed: f32,
+    pub mass: f32,
+}
+
+impl From<Stats> for super::types::Stats {
+    fn from(stats: Stats) -> Self {
+        Self {
+            strength: stats.strength,
+            regen_rate: stats.regen_rate,
+            health: stats.health,
+            max_health: stats.max_health,
+            def: stats.def,
+            speed: stats.speed,
+            mass: stats.mass,
+            resistances: Resistances::default(),
+        }
+    }
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = next::Save {
+        player: next::PlayerSave {
+            player: save.player.player,
+            stats: save.player.stats.into(),
+            pos: save.player.pos,
+            effects: save.player.effects,
+            facing_left: save.player.facing_left,
+            level: save.player.level,
+        },
+        worlds: save
+            .worlds
+            .into_iter()
+            .map(|(world, world_save)| {
+                let world_save = next::WorldSave {
+                    mobs: world_save
+                        .mobs
+                        .into_iter()
+                        .map(|mob| next::MobSave {
+                            data: mob.data,
+                            stats: mob.stats.into(),
+                            pos: mob.pos,
+                        })
+                        .collect(),
+                    items: world_save.items,
+                    available_chests: world_save.available_chests,
+                    objects: world_save.objects,
+                    weather: world_save.weather,
+                    dungeon_seed: world_save.dungeon_seed,
+                };
+                (world, world_save)
+            })
+            .collect(),
+        current_world: save.current_world,
+        clock: save.clock,
+        seed: save.seed,
+        defeated_bosses: save.defeated_bosses,
+        checkpoint: save.checkpoint,
+        deaths: save.deaths,
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
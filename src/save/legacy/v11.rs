@@ -0,0 +1,81 @@
+//! Version 11, before saves kept the state of the world rng
+
+pub use super::types::{CheckpointSave, DeathSave, ItemSave, MapObjectSave};
+use super::types::{
+    EffectsController, MobObject, Player, PlayerLevel, Stats, World, WorldClock, WorldWeather,
+};
+use crate::save::SaveError;
+use bevy::{prelude::*, utils::hashbrown::HashMap};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+    pub clock: WorldClock,
+    pub seed: u64,
+    pub defeated_bosses: Vec<String>,
+    pub checkpoint: Option<CheckpointSave>,
+    pub deaths: Vec<DeathSave>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlayerSave {
+    pub player: Player,
+    pub stats: Stats,
+    pub pos: Vec2,
+    pub effects: EffectsController,
+    pub facing_left: bool,
+    pub level: PlayerLevel,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorldSave {
+    pub mobs: Vec<MobSave>,
+    pub items: Vec<ItemSave>,
+    pub available_chests: Option<Vec<String>>,
+    pub objects: HashMap<u32, MapObjectSave>,
+    pub weather: Option<WorldWeather>,
+    pub dungeon_seed: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MobSave {
+    pub data: MobObject,
+    pub stats: Stats,
+    pub pos: Vec2,
+}
+
+/// Layout of version 12
+#[derive(Serialize)]
+struct Upgraded {
+    player: PlayerSave,
+    worlds: HashMap<World, WorldSave>,
+    current_world: World,
+    clock: WorldClock,
+    seed: u64,
+    defeated_bosses: Vec<String>,
+    checkpoint: Option<CheckpointSave>,
+    deaths: Vec<DeathSave>,
+    rng_state: u64,
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = Upgraded {
+        player: save.player,
+        worlds: save.worlds,
+        current_world: save.current_world,
+        clock: save.clock,
+        seed: save.seed,
+        defeated_bosses: save.defeated_bosses,
+        checkpoint: save.checkpoint,
+        deaths: save.deaths,
+        // The world rng of older saves restarted from the seed
+        rng_state: save.seed,
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
@@ -0,0 +1,46 @@
+//! Version 2, before the state of map objects was saved
+
+use super::types::World;
+use super::v3 as next;
+pub use super::v3::PlayerSave;
+use crate::save::SaveError;
+use bevy::utils::hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorldSave {
+    pub mobs: Vec<next::MobSave>,
+    pub items: Vec<next::ItemSave>,
+    pub available_chests: Option<Vec<String>>,
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = next::Save {
+        player: save.player,
+        worlds: save
+            .worlds
+            .into_iter()
+            .map(|(world, world_save)| {
+                let world_save = next::WorldSave {
+                    mobs: world_save.mobs,
+                    items: world_save.items,
+                    available_chests: world_save.available_chests,
+                    objects: HashMap::new(),
+                };
+                (world, world_save)
+            })
+            .collect(),
+        current_world: save.current_world,
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
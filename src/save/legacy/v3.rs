@@ -0,0 +1,29 @@
+//! Version 3, before the world clock was saved
+
+use super::types::{World, WorldClock};
+use super::v4 as next;
+pub use super::v4::{ItemSave, MobSave, PlayerSave, WorldSave};
+use crate::save::SaveError;
+use bevy::utils::hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = next::Save {
+        player: save.player,
+        worlds: save.worlds,
+        current_world: save.current_world,
+        // The clock of new worlds
+        clock: WorldClock { day: 0, time: 0.3 },
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
@@ -0,0 +1,50 @@
+//! Version 4, before the weather of each world was saved
+
+use super::types::{World, WorldClock};
+use super::v5 as next;
+pub use super::v5::{ItemSave, MapObjectSave, MobSave, PlayerSave};
+use crate::save::SaveError;
+use bevy::utils::hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+    pub clock: WorldClock,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorldSave {
+    pub mobs: Vec<MobSave>,
+    pub items: Vec<ItemSave>,
+    pub available_chests: Option<Vec<String>>,
+    pub objects: HashMap<u32, MapObjectSave>,
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = next::Save {
+        player: save.player,
+        worlds: save
+            .worlds
+            .into_iter()
+            .map(|(world, world_save)| {
+                let world_save = next::WorldSave {
+                    mobs: world_save.mobs,
+                    items: world_save.items,
+                    available_chests: world_save.available_chests,
+                    objects: world_save.objects,
+                    weather: None,
+                };
+                (world, world_save)
+            })
+            .collect(),
+        current_world: save.current_world,
+        clock: save.clock,
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
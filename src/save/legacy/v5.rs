@@ -0,0 +1,62 @@
+//! Version 5, before the seed of generated dungeons was saved
+
+use super::types::{Biome, World, WorldClock, WorldWeather};
+use super::v6 as next;
+pub use super::v6::{ItemSave, MapObjectSave, MobSave, PlayerSave};
+use crate::save::SaveError;
+use bevy::{prelude::Vec2, utils::hashbrown::HashMap};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+    pub clock: WorldClock,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorldSave {
+    pub mobs: Vec<MobSave>,
+    pub items: Vec<ItemSave>,
+    pub available_chests: Option<Vec<String>>,
+    pub objects: HashMap<u32, MapObjectSave>,
+    pub weather: Option<WorldWeather>,
+}
+
+/// Where the player of a new world appears
+const START_POS: Vec2 = Vec2::new(0., 16.);
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let mut save: Save = bincode::deserialize(payload)?;
+
+    // The hand made pyramid can't be mapped to a generated layout, it starts over
+    if let World::Dungeon(_) = save.current_world {
+        save.current_world = World::Biome(Biome::Plains);
+        save.player.pos = START_POS;
+    }
+
+    let upgraded = next::Save {
+        player: save.player,
+        worlds: save
+            .worlds
+            .into_iter()
+            .filter(|(world, _)| matches!(world, World::Biome(_)))
+            .map(|(world, world_save)| {
+                let world_save = next::WorldSave {
+                    mobs: world_save.mobs,
+                    items: world_save.items,
+                    available_chests: world_save.available_chests,
+                    objects: world_save.objects,
+                    weather: world_save.weather,
+                    dungeon_seed: None,
+                };
+                (world, world_save)
+            })
+            .collect(),
+        current_world: save.current_world,
+        clock: save.clock,
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
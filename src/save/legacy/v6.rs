@@ -0,0 +1,31 @@
+//! Version 6, before saves had a world seed
+
+use super::types::{World, WorldClock};
+use super::v7 as next;
+pub use super::v7::{ItemSave, MapObjectSave, MobSave, PlayerSave, WorldSave};
+use crate::{random::WorldRng, save::SaveError};
+use bevy::utils::hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+    pub clock: WorldClock,
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = next::Save {
+        player: save.player,
+        worlds: save.worlds,
+        current_world: save.current_world,
+        clock: save.clock,
+        // Loading the same old save twice must give the same world
+        seed: WorldRng::seed_from_bytes(payload),
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
@@ -0,0 +1,32 @@
+//! Version 7, before saves recorded the defeated bosses
+
+use super::types::{World, WorldClock};
+use super::v8 as next;
+pub use super::v8::{ItemSave, MapObjectSave, MobSave, PlayerSave, WorldSave};
+use crate::save::SaveError;
+use bevy::utils::hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+    pub clock: WorldClock,
+    pub seed: u64,
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = next::Save {
+        player: save.player,
+        worlds: save.worlds,
+        current_world: save.current_world,
+        clock: save.clock,
+        seed: save.seed,
+        defeated_bosses: Vec::new(),
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
@@ -0,0 +1,35 @@
+//! Version 8, before saves recorded checkpoints and deaths
+
+use super::types::{World, WorldClock};
+use super::v9 as next;
+pub use super::v9::{ItemSave, MapObjectSave, MobSave, PlayerSave, WorldSave};
+use crate::save::SaveError;
+use bevy::utils::hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+    pub clock: WorldClock,
+    pub seed: u64,
+    pub defeated_bosses: Vec<String>,
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = next::Save {
+        player: save.player,
+        worlds: save.worlds,
+        current_world: save.current_world,
+        clock: save.clock,
+        seed: save.seed,
+        defeated_bosses: save.defeated_bosses,
+        checkpoint: None,
+        deaths: Vec::new(),
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
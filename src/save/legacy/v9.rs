@@ -0,0 +1,60 @@
+//! Version 9, before the player had a level
+
+use super::types::{EffectsController, Player, PlayerLevel, StatPoints, World, WorldClock};
+use super::v10 as next;
+pub use super::v10::{
+    CheckpointSave, DeathSave, ItemSave, MapObjectSave, MobSave, Stats, WorldSave,
+};
+use crate::save::SaveError;
+use bevy::{prelude::*, utils::hashbrown::HashMap};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    pub player: PlayerSave,
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+    pub clock: WorldClock,
+    pub seed: u64,
+    pub defeated_bosses: Vec<String>,
+    pub checkpoint: Option<CheckpointSave>,
+    pub deaths: Vec<DeathSave>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlayerSave {
+    pub player: Player,
+    pub stats: Stats,
+    pub pos: Vec2,
+    pub effects: EffectsController,
+    pub facing_left: bool,
+}
+
+pub fn migrate(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let save: Save = bincode::deserialize(payload)?;
+
+    let upgraded = next::Save {
+        player: next::PlayerSave {
+            player: save.player.player,
+            stats: save.player.stats,
+            pos: save.player.pos,
+            effects: save.player.effects,
+            facing_left: save.player.facing_left,
+            level: PlayerLevel {
+                level: 1,
+                xp: 0,
+                stat_points: 0,
+                spent: StatPoints::default(),
+            },
+        },
+        worlds: save.worlds,
+        current_world: save.current_world,
+        clock: save.clock,
+        seed: save.seed,
+        defeated_bosses: save.defeated_bosses,
+        checkpoint: save.checkpoint,
+        deaths: save.deaths,
+    };
+
+    Ok(bincode::serialize(&upgraded)?)
+}
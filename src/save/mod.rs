@@ -0,0 +1,749 @@
+pub mod archive;
+pub mod legacy;
+pub mod version;
+
+use crate::{
+    background::ParallaxBackground,
+    boss::Boss,
+    chest::Chest,
+    day_night::WorldClock,
+    dungeon::DungeonSeed,
+    effects::EffectsController,
+    gui::main_menu::MainMenuState,
+    items::stack::ItemStack,
+    mob::{definition::MobDefinitions, list::MobObject, MobBundle, MobTrait},
+    ore::{MinableOre, Ore},
+    player::{class::PlayerClasses, level::PlayerLevel, Player},
+    random::WorldRng,
+    state::AppState,
+    stats::Stats,
+    tiled::Loaded,
+    weather::WorldWeather,
+    world::{World, BLOCK_SIZE},
+    CONFIG_DIR,
+};
+use bevy::{
+    ecs::system::SystemParam, prelude::*, utils::hashbrown::HashMap, window::WindowCloseRequested,
+};
+use chrono::{DateTime, TimeZone};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::{Ordering, Reverse},
+    fs,
+    io::Write,
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+use thiserror::Error;
+
+pub struct SavePlugin;
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveData>()
+            .add_event::<SaveWorldEvent>()
+            .init_resource::<CurrentSave>()
+            .insert_resource(AutosaveTimer(Timer::from_seconds(
+                AutosaveTimer::SECONDS,
+                TimerMode::Repeating,
+            )))
+            .add_systems(Update, set_current_save)
+            .add_systems(Update, autosave.run_if(in_state(AppState::InGame)))
+            .add_systems(
+                Update,
+                save_requested
+                    .after(autosave)
+                    .run_if(in_state(AppState::InGame).or_else(in_state(AppState::Paused))),
+            )
+            .add_systems(
+                OnTransition {
+                    from: AppState::Paused,
+                    to: AppState::MainMenu(MainMenuState::Default),
+                },
+                save_world,
+            );
+    }
+}
+
+/// Asks to write the current world to disk without leaving it
+#[derive(Event)]
+pub struct SaveWorldEvent;
+
+#[derive(Resource)]
+pub struct AutosaveTimer(pub Timer);
+
+impl AutosaveTimer {
+    const SECONDS: f32 = 120.;
+}
+
+fn set_current_save(
+    mut event: EventReader<SaveData>,
+    mut current: ResMut<CurrentSave>,
+    mut autosave_timer: ResMut<AutosaveTimer>,
+) {
+    for ev in event.read() {
+        *current = CurrentSave(Some(ev.clone()));
+        autosave_timer.0.reset();
+    }
+}
+
+fn autosave(
+    time: Res<Time>,
+    mut autosave_timer: ResMut<AutosaveTimer>,
+    mut save_event: EventWriter<SaveWorldEvent>,
+) {
+    autosave_timer.0.tick(time.delta());
+    if autosave_timer.0.just_finished() {
+        save_event.send(SaveWorldEvent);
+    }
+}
+
+fn save_requested(
+    mut save_event: EventReader<SaveWorldEvent>,
+    mut close_event: EventReader<WindowCloseRequested>,
+    snapshot: WorldSnapshot,
+    mut current_save: ResMut<CurrentSave>,
+    mut rng: ResMut<WorldRng>,
+) {
+    let requested = save_event.read().count() + close_event.read().count() > 0;
+    if !requested {
+        return;
+    }
+
+    let Some(save_data) = &mut current_save.0 else {
+        return;
+    };
+
+    let Some(mut save) = snapshot.save(&save_data.data) else {
+        warn!("World {} isn't loaded yet, skipping save", save_data.ident);
+        return;
+    };
+    save.rng_state = rng.reseed();
+
+    match save.save_world(&save_data.ident) {
+        Ok(()) => save_data.data = save,
+        Err(e) => error!("Failed to save world {} : {e}", save_data.ident),
+    }
+}
+
+fn save_world(
+    mut commands: Commands,
+    snapshot: WorldSnapshot,
+    mut current_save: ResMut<CurrentSave>,
+    mut rng: ResMut<WorldRng>,
+) {
+    if let Some(save_data) = current_save.0.take() {
+        info!("Saving world : {}", save_data.ident);
+
+        if let Some(mut save) = snapshot.save(&save_data.data) {
+            save.rng_state = rng.reseed();
+            if let Err(e) = save.save_world(&save_data.ident) {
+                error!("Failed to save world {} : {e}", save_data.ident);
+            }
+        }
+
+        snapshot.despawn(&mut commands);
+    }
+}
+
+/// Every entity of the current world that ends up in the [`Save`]
+#[derive(SystemParam)]
+pub struct WorldSnapshot<'w, 's> {
+    player: Query<
+        'w,
+        's,
+        (
+            &'static Player,
+            &'static Transform,
+            &'static Stats,
+            &'static EffectsController,
+            &'static TextureAtlasSprite,
+            &'static PlayerLevel,
+        ),
+    >,
+    mobs: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static MobObject,
+            &'static Transform,
+            &'static Stats,
+            Has<Boss>,
+        ),
+    >,
+    items: Query<'w, 's, (Entity, &'static ItemStack, &'static Transform)>,
+    chests: Query<'w, 's, &'static Chest>,
+    ores: Query<'w, 's, &'static MinableOre>,
+    world: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static World,
+            Has<Loaded>,
+            Option<&'static WorldWeather>,
+            Option<&'static DungeonSeed>,
+        ),
+    >,
+    backgrounds: Query<'w, 's, Entity, With<ParallaxBackground>>,
+    clock: Res<'w, WorldClock>,
+}
+
+impl WorldSnapshot<'_, '_> {
+    pub fn world_save(&self) -> WorldSave {
+        WorldSave {
+            items: self
+                .items
+                .iter()
+                .map(|(_, stack, transform)| ItemSave {
+                    stack: stack.clone(),
+                    pos: transform.translation.xy(),
+                })
+                .collect(),
+
+            mobs: self
+                .mobs
+                .iter()
+                // Bosses are spawned again by their arena
+                .filter(|(_, _, _, _, boss)| !boss)
+                .map(|(_, mob, transform, stats, _)| MobSave {
+                    data: mob.clone(),
+                    stats: stats.clone(),
+                    pos: transform.translation.xy(),
+                })
+                .collect(),
+
+            available_chests: Some(self.chests.iter().map(|chest| chest.name.clone()).collect()),
+
+            objects: self
+                .ores
+                .iter()
+                .map(|ore| {
+                    (
+                        ore.object_id,
+                        MapObjectSave::Ore {
+                            current_ore: ore.current_ore.clone(),
+                            regrow_at: ore.regrow_at,
+                        },
+                    )
+                })
+                .collect(),
+
+            weather: self
+                .world
+                .get_single()
+                .ok()
+                .and_then(|(_, _, _, weather, _)| weather.cloned()),
+
+            dungeon_seed: self
+                .world
+                .get_single()
+                .ok()
+                .and_then(|(_, _, _, _, seed)| seed.map(|seed| seed.0)),
+        }
+    }
+
+    /// # Returns
+    /// `previous` updated with the current state of the world, [`None`] if the world isn't loaded
+    pub fn save(&self, previous: &Save) -> Option<Save> {
+        let (player, player_transform, stats, effects, sprite, level) =
+            self.player.get_single().ok()?;
+        let (_, world, loaded, _, _) = self.world.get_single().ok()?;
+        if !loaded {
+            return None;
+        }
+
+        let mut save = previous.clone();
+        save.worlds.insert(world.clone(), self.world_save());
+        save.player = PlayerSave {
+            player: player.clone(),
+            stats: stats.clone(),
+            pos: player_transform.translation.xy(),
+            effects: effects.clone(),
+            facing_left: sprite.flip_x,
+            level: level.clone(),
+        };
+        save.current_world = world.clone();
+        save.clock = self.clock.clone();
+
+        Some(save)
+    }
+
+    pub fn despawn(&self, commands: &mut Commands) {
+        let entities = self
+            .items
+            .iter()
+            .map(|(entity, _, _)| entity)
+            .chain(self.mobs.iter().map(|(entity, _, _, _, _)| entity))
+            .chain(self.world.iter().map(|(entity, _, _, _, _)| entity))
+            .chain(self.backgrounds.iter());
+
+        for entity in entities {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CurrentSave(pub Option<SaveData>);
+
+#[derive(Event, Clone)]
+pub struct SaveData {
+    pub ident: String,
+    pub data: Save,
+}
+
+impl SaveData {
+    pub fn read(&self) -> &Save {
+        &self.data
+    }
+
+    pub fn new(ident: &str, data: Save) -> Self {
+        Self {
+            ident: ident.to_owned(),
+            data,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MobSave {
+    pub data: MobObject,
+    pub stats: Stats,
+    pub pos: Vec2,
+}
+
+impl MobSave {
+    pub fn into_bundle(
+        &self,
+        asset_server: &Res<AssetServer>,
+        definitions: &MobDefinitions,
+    ) -> MobBundle {
+        definitions
+            .resolve(self.data.clone())
+            .bundle(asset_server, self.pos)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PlayerSave {
+    pub player: Player,
+    pub stats: Stats,
+    pub pos: Vec2,
+    pub effects: EffectsController,
+    pub facing_left: bool,
+    pub level: PlayerLevel,
+}
+
+impl Default for PlayerSave {
+    fn default() -> Self {
+        Self {
+            player: Player::default(),
+            stats: Stats::default(),
+            // Spawn one block above the ground
+            pos: Vec2::new(0.0, BLOCK_SIZE),
+            effects: EffectsController::default(),
+            facing_left: false,
+            level: PlayerLevel::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ItemSave {
+    pub stack: ItemStack,
+    pub pos: Vec2,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WorldSave {
+    pub mobs: Vec<MobSave>,
+    pub items: Vec<ItemSave>,
+    pub available_chests: Option<Vec<String>>,
+    /// State of the objects placed in the Tiled map, keyed by their object id
+    pub objects: HashMap<u32, MapObjectSave>,
+    /// [`None`] starts a new weather schedule
+    pub weather: Option<WorldWeather>,
+    /// Seed of the layout of a generated dungeon
+    pub dungeon_seed: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum MapObjectSave {
+    Ore {
+        current_ore: Ore,
+        /// When the ore grows back, [`None`] if it hasn't been mined
+        regrow_at: Option<DateTime<chrono::Local>>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Default, Resource, Clone)]
+pub struct Save {
+    pub player: PlayerSave,
+    #[serde(with = "map_as_entries")]
+    pub worlds: HashMap<World, WorldSave>,
+    pub current_world: World,
+    pub clock: WorldClock,
+    /// Seed of the [`WorldRng`](crate::random::WorldRng), chosen when the world is created
+    pub seed: u64,
+    /// [`BossTrait::name`](crate::boss::BossTrait::name) of the bosses that won't spawn again
+    pub defeated_bosses: Vec<String>,
+    /// Last checkpoint reached, the player respawns at the spawn of the current world if [`None`]
+    pub checkpoint: Option<CheckpointSave>,
+    pub deaths: Vec<DeathSave>,
+    /// Where the [`WorldRng`](crate::random::WorldRng) restarts from on load, drawn from it on every save
+    pub rng_state: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CheckpointSave {
+    pub world: World,
+    /// Name of the [`SpawnPoint`](crate::portal::SpawnPoint) of the checkpoint
+    pub spawn_point: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DeathSave {
+    pub world: World,
+    pub pos: Vec2,
+    pub date: DateTime<chrono::Local>,
+    pub money_lost: u64,
+    /// Stacks left where the player died
+    pub items_dropped: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("Could not access save file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not read save data: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("Could not read save metadata: {0}")]
+    MetaData(#[from] serde_json::Error),
+    #[error("Save version {0} is newer than this game supports")]
+    UnsupportedVersion(u32),
+    #[error("Save already exists")]
+    AlreadyExists,
+    #[error("Invalid world archive : {0}")]
+    InvalidArchive(&'static str),
+    #[error("Invalid world name")]
+    InvalidName,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SaveMetaData {
+    pub name: String,
+    pub creation_date: DateTime<chrono::Local>,
+    pub last_played: DateTime<chrono::Local>,
+}
+
+impl SaveMetaData {
+    const FILE_NAME: &'static str = "metadata.json";
+
+    pub fn from_save_path(path: &Path) -> Result<Self, SaveError> {
+        let data = fs::read(path.join(Self::FILE_NAME))?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    pub fn new_now(name: &str) -> Self {
+        let date = chrono::offset::Local::now();
+
+        Self {
+            name: name.to_owned(),
+            creation_date: date,
+            last_played: date,
+        }
+    }
+
+    pub fn save(&self, save_path: &Path) -> Result<(), SaveError> {
+        let meta_str = serde_json::to_string(&self)?;
+        write_atomic(
+            &save_path.join(SaveMetaData::FILE_NAME),
+            meta_str.as_bytes(),
+        )?;
+        Ok(())
+    }
+}
+
+pub static SAVES_DIR: Lazy<PathBuf> = Lazy::new(|| CONFIG_DIR.join("saves"));
+
+impl Save {
+    const FILE_NAME: &'static str = "world";
+    pub const BACKUP_COUNT: usize = 5;
+    /// Saves made sooner after the latest backup don't make a new one, so autosaves keep older backups around
+    const BACKUP_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+    pub fn read(name: &str) -> Result<Self, SaveError> {
+        let path = SAVES_DIR.join(name).join(Self::FILE_NAME);
+        let data = fs::read(path)?;
+        Self::decode(&data)
+    }
+
+    /// Reads a save of any known version, migrating it to the current layout
+    pub fn decode(data: &[u8]) -> Result<Self, SaveError> {
+        version::decode(data)
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, SaveError> {
+        version::encode(self)
+    }
+
+    pub fn new(
+        name: &str,
+        class: PlayerClasses,
+        seed: u64,
+    ) -> Result<(Self, SaveMetaData), SaveError> {
+        let path = SAVES_DIR.join(name);
+        if path.exists() {
+            return Err(SaveError::AlreadyExists);
+        }
+
+        fs::create_dir_all(&path)?;
+
+        let meta = SaveMetaData::new_now(name);
+        meta.save(&path)?;
+
+        let mut save = Self {
+            seed,
+            rng_state: seed,
+            ..Default::default()
+        };
+        save.player.player.class = class;
+
+        Ok((save, meta))
+    }
+
+    pub fn save_world(&self, name: &str) -> Result<(), SaveError> {
+        let path = SAVES_DIR.join(name);
+        let data = self.encode()?;
+
+        let latest_backup_age = Self::list_backups(&path)
+            .first()
+            .and_then(|(_, date)| (chrono::offset::Local::now() - *date).to_std().ok());
+        if latest_backup_age.is_none_or(|age| age >= Self::BACKUP_INTERVAL) {
+            Self::rotate_backups(&path)?;
+        }
+        write_atomic(&path.join(Self::FILE_NAME), &data)?;
+
+        let mut meta = SaveMetaData::from_save_path(&path)?;
+        meta.last_played = chrono::offset::Local::now();
+        meta.save(&path)?;
+
+        info!("Saved world {} successfully", name);
+        Ok(())
+    }
+
+    /// Backups are named after the time they were made, so their date survives copies and archives
+    fn backup_path(save_path: &Path, date: DateTime<chrono::Local>) -> PathBuf {
+        save_path.join(format!(
+            "{}.backup.{}",
+            Self::FILE_NAME,
+            date.timestamp_millis()
+        ))
+    }
+
+    /// # Returns
+    /// The path and date of every backup of the save, most recent first
+    fn list_backups(save_path: &Path) -> Vec<(PathBuf, DateTime<chrono::Local>)> {
+        let prefix = format!("{}.backup.", Self::FILE_NAME);
+        let Ok(dir) = save_path.read_dir() else {
+            return Vec::new();
+        };
+
+        let mut backups = dir
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let millis = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix(&prefix)?
+                    .parse()
+                    .ok()?;
+                let date = chrono::Local.timestamp_millis_opt(millis).single()?;
+                Some((entry.path(), date))
+            })
+            .collect::<Vec<_>>();
+        backups.sort_by_key(|(_, date)| Reverse(*date));
+        backups
+    }
+
+    /// Copies the world file into a new backup and drops the oldest ones past [`Self::BACKUP_COUNT`]
+    fn rotate_backups(save_path: &Path) -> Result<(), SaveError> {
+        let world_path = save_path.join(Self::FILE_NAME);
+        if !world_path.exists() {
+            return Ok(());
+        }
+
+        write_atomic(
+            &Self::backup_path(save_path, chrono::offset::Local::now()),
+            &fs::read(world_path)?,
+        )?;
+
+        for (backup_path, _) in Self::list_backups(save_path)
+            .into_iter()
+            .skip(Self::BACKUP_COUNT)
+        {
+            fs::remove_file(backup_path)?;
+        }
+        Ok(())
+    }
+
+    /// # Returns
+    /// The index and date of every backup of the save, most recent first
+    pub fn get_backups(name: &str) -> Vec<(usize, DateTime<chrono::Local>)> {
+        Self::list_backups(&SAVES_DIR.join(name))
+            .into_iter()
+            .enumerate()
+            .map(|(index, (_, date))| (index + 1, date))
+            .collect()
+    }
+
+    /// Replaces the world file with one of its backups, the replaced world becomes the latest backup
+    pub fn restore_backup(name: &str, index: usize) -> Result<(), SaveError> {
+        let path = SAVES_DIR.join(name);
+        let (backup_path, _) = index
+            .checked_sub(1)
+            .and_then(|index| Self::list_backups(&path).into_iter().nth(index))
+            .ok_or(std::io::Error::from(std::io::ErrorKind::NotFound))?;
+        let data = fs::read(backup_path)?;
+
+        // Make sure the backup is readable before touching the world file
+        Self::decode(&data)?;
+
+        Self::rotate_backups(&path)?;
+        write_atomic(&path.join(Self::FILE_NAME), &data)?;
+        Ok(())
+    }
+
+    pub fn get_saves() -> Vec<Result<(String, SaveMetaData), String>> {
+        let dir_res = SAVES_DIR.read_dir().map_err(|e| e.to_string());
+        if let Ok(dir) = dir_res {
+            let mut data = dir
+                .into_iter()
+                .map(|entry| {
+                    let folder = entry.map_err(|e| e.to_string())?;
+                    let path = folder.path();
+                    let file_name = folder.file_name().to_string_lossy().to_string();
+
+                    let metadata =
+                        SaveMetaData::from_save_path(&path).map_err(|e| e.to_string())?;
+                    let world_save_path = path.join(Save::FILE_NAME);
+
+                    if !world_save_path.exists() {
+                        return Err("ui.main_menu.load_save.err.no_world".into());
+                    }
+
+                    Ok((file_name, metadata))
+                })
+                .collect::<Vec<Result<_, _>>>();
+
+            data.sort_by(|a, b| match (a, b) {
+                (Ok((_, meta_a)), Ok((_, meta_b))) => meta_b
+                    .last_played
+                    .timestamp()
+                    .partial_cmp(&meta_a.last_played.timestamp())
+                    .unwrap(),
+                _ => Ordering::Equal,
+            });
+
+            data
+        } else {
+            fs::create_dir_all(&*SAVES_DIR).unwrap();
+            Vec::new()
+        }
+    }
+
+    pub fn delete(name: &str) -> Result<(), String> {
+        fs::remove_dir_all(SAVES_DIR.join(name)).map_err(|e| e.to_string())
+    }
+
+    /// Moves the save folder and updates the name stored in its metadata
+    pub fn rename(name: &str, new_name: &str) -> Result<(), SaveError> {
+        if !Self::is_valid_name(new_name) {
+            return Err(SaveError::InvalidName);
+        }
+
+        let new_path = SAVES_DIR.join(new_name);
+        if name != new_name {
+            if new_path.exists() {
+                return Err(SaveError::AlreadyExists);
+            }
+            fs::rename(SAVES_DIR.join(name), &new_path)?;
+        }
+
+        let mut meta = SaveMetaData::from_save_path(&new_path)?;
+        meta.name = new_name.to_owned();
+        meta.save(&new_path)
+    }
+
+    /// Copies the world and its backups into a new save created now
+    /// # Returns
+    /// The name of the copy
+    pub fn duplicate(name: &str) -> Result<String, SaveError> {
+        let path = SAVES_DIR.join(name);
+        let new_name = Self::available_name(name);
+        let new_path = SAVES_DIR.join(&new_name);
+
+        fs::create_dir_all(&new_path)?;
+        for entry in path.read_dir()? {
+            let entry = entry?;
+            if entry.file_name() != SaveMetaData::FILE_NAME {
+                fs::copy(entry.path(), new_path.join(entry.file_name()))?;
+            }
+        }
+
+        let mut meta = SaveMetaData::new_now(&new_name);
+        meta.last_played = SaveMetaData::from_save_path(&path)?.last_played;
+        meta.save(&new_path)?;
+
+        Ok(new_name)
+    }
+
+    /// A save name is used as a folder name so it must be a single path component
+    pub fn is_valid_name(name: &str) -> bool {
+        let mut components = Path::new(name).components();
+        matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        )
+    }
+}
+
+/// Writes into a temporary file then renames it so a crash mid-write can't leave a truncated file
+pub fn write_atomic(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+
+    fs::rename(tmp_path, path)
+}
+
+/// Serializes a map as a list of `(key, value)` entries so it can be written to json even when its keys aren't strings,
+/// bincode encodes both the same way
+pub mod map_as_entries {
+    use bevy::utils::hashbrown::HashMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::hash::Hash;
+
+    pub fn serialize<S, K, V>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        K: Serialize,
+        V: Serialize,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, D, K, V>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+    {
+        let entries = Vec::<(K, V)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
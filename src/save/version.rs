@@ -0,0 +1,170 @@
+use super::{legacy, Save, SaveError};
+use serde::{Deserialize, Serialize};
+
+/// Version written by this build of the game, bump it every time the layout of [`Save`] changes
+pub const CURRENT_VERSION: u32 = 12;
+
+/// Marks a versioned save file, unversioned saves (version 0) start directly with the [`Save`] payload
+const MAGIC: [u8; 4] = *b"TRDV";
+
+/// Upgrades a payload of version `n` into a payload of version `n + 1`
+type Migration = fn(&[u8]) -> Result<Vec<u8>, SaveError>;
+
+/// `MIGRATIONS[n]` upgrades version `n` to version `n + 1`
+const MIGRATIONS: [Migration; CURRENT_VERSION as usize] = [
+    from_unversioned,
+    legacy::v1::migrate,
+    legacy::v2::migrate,
+    legacy::v3::migrate,
+    legacy::v4::migrate,
+    legacy::v5::migrate,
+    legacy::v6::migrate,
+    legacy::v7::migrate,
+    legacy::v8::migrate,
+    legacy::v9::migrate,
+    legacy::v10::migrate,
+    legacy::v11::migrate,
+];
+
+#[derive(Serialize, Deserialize)]
+struct SaveEnvelope {
+    magic: [u8; 4],
+    version: u32,
+    payload: Vec<u8>,
+}
+
+pub fn encode(save: &Save) -> Result<Vec<u8>, SaveError> {
+    let envelope = SaveEnvelope {
+        magic: MAGIC,
+        version: CURRENT_VERSION,
+        payload: bincode::serialize(save)?,
+    };
+
+    Ok(bincode::serialize(&envelope)?)
+}
+
+pub fn decode(data: &[u8]) -> Result<Save, SaveError> {
+    let (mut version, mut payload) = if data.starts_with(&MAGIC) {
+        let envelope: SaveEnvelope = bincode::deserialize(data)?;
+        (envelope.version, envelope.payload)
+    } else {
+        (0, data.to_vec())
+    };
+
+    if version > CURRENT_VERSION {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+
+    while version < CURRENT_VERSION {
+        payload = MIGRATIONS[version as usize](&payload)?;
+        version += 1;
+    }
+
+    Ok(bincode::deserialize(&payload)?)
+}
+
+/// Saves written before the envelope existed share the layout of version 1
+fn from_unversioned(payload: &[u8]) -> Result<Vec<u8>, SaveError> {
+    Ok(payload.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use bevy::math::Vec2;
+
+    use super::{decode, CURRENT_VERSION};
+    use crate::{
+        effects::Effect,
+        items::list::Item,
+        mob::MobTrait,
+        player::class::PlayerClasses,
+        world::{ForestBiome, PlainsBiome, World},
+    };
+
+    /// Save written by the given version of the game, kept in `tests/fixtures/saves/`
+    fn fixture(version: u32) -> Vec<u8> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/saves")
+            .join(format!("v{version}.bin"));
+        fs::read(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+    }
+
+    /// Every older version must keep loading into the current layout
+    #[test]
+    fn older_versions_migrate() {
+        let forest = World::Biome(ForestBiome.into());
+        let plains = World::Biome(PlainsBiome.into());
+
+        for version in 0..CURRENT_VERSION {
+            let save = decode(&fixture(version))
+                .unwrap_or_else(|e| panic!("version {version} can't be read: {e}"));
+
+            let player = &save.player;
+            assert!(matches!(player.player.class, PlayerClasses::Wizard(_)));
+            let stack = player.player.inventory.ressources[0]
+                .item
+                .as_ref()
+                .expect("the inventory is lost");
+            assert!(matches!(stack.item, Item::ManaPotion(_)));
+            assert_eq!(stack.count, 3);
+            assert_eq!(player.stats.health, 13., "version {version}");
+            assert_eq!(player.stats.max_health, 25.);
+            assert_eq!(player.stats.strength, 2.);
+            let resistances = &player.stats.resistances;
+            assert_eq!(
+                [
+                    resistances.physical,
+                    resistances.fire,
+                    resistances.poison,
+                    resistances.magic
+                ],
+                [0.; 4]
+            );
+            assert_eq!(player.pos, Vec2::new(64., 32.));
+            assert!(player.effects.get_effect(&Effect::Levitation).is_some());
+            assert_eq!(player.facing_left, version >= 2, "version {version}");
+            assert_eq!(player.level.level, if version >= 10 { 3 } else { 1 });
+
+            assert!(save.current_world == forest);
+            assert_eq!(save.worlds.len(), 2, "version {version}");
+            assert!(save.worlds.contains_key(&plains));
+            let world = &save.worlds[&forest];
+            assert_eq!(world.mobs.len(), 1, "version {version}");
+            assert_eq!(world.mobs[0].data.name(), "pig");
+            assert_eq!(world.mobs[0].stats.health, 15.);
+            assert_eq!(world.items.len(), 1, "version {version}");
+            assert_eq!(world.available_chests, Some(vec!["chest_1".to_owned()]));
+            assert_eq!(
+                world.objects.len(),
+                usize::from(version >= 3),
+                "version {version}"
+            );
+            assert!(world.weather.is_none());
+            assert!(world.dungeon_seed.is_none());
+
+            if version >= 4 {
+                assert_eq!(save.clock.day, 4, "version {version}");
+            }
+            if version >= 7 {
+                assert_eq!(save.seed, 1234, "version {version}");
+            } else {
+                let again = decode(&fixture(version)).unwrap();
+                assert_eq!(save.seed, again.seed, "version {version}");
+            }
+            let bosses: &[&str] = if version >= 8 { &["pharaoh"] } else { &[] };
+            assert_eq!(save.defeated_bosses, bosses, "version {version}");
+            match &save.checkpoint {
+                Some(checkpoint) => {
+                    assert!(version >= 9, "version {version} got a checkpoint");
+                    assert!(checkpoint.world == plains);
+                    assert_eq!(checkpoint.spawn_point, "camp");
+                }
+                None => assert!(version < 9, "version {version} lost its checkpoint"),
+            }
+            assert!(save.deaths.is_empty());
+            assert_eq!(save.rng_state, save.seed, "version {version}");
+        }
+    }
+}
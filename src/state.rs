@@ -7,6 +7,8 @@ pub enum AppState {
     MainMenu(MainMenuState),
     InGame,
     Paused,
+    /// Death screen, the world stays loaded behind it until the player respawns
+    Dead,
 }
 
 impl Default for AppState {
@@ -1,12 +1,14 @@
-use std::f32::consts::PI;
-
 use bevy::prelude::*;
-use rand::random;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    animation::AnimationController, gui::styles::text_style, mob::Mob,
-    player::money::DropMoneyEvent, state::AppState,
+    animation::AnimationController,
+    damage::DamageType,
+    effects::{EffectsController, Modifier},
+    mob::{list::MobObject, Mob, MobTrait},
+    player::{death::PlayerDeathEvent, level::XpEvent, money::DropMoneyEvent, Player},
+    random::WorldRng,
+    state::AppState,
 };
 
 pub struct StatsPlugin;
@@ -16,25 +18,25 @@ impl Plugin for StatsPlugin {
     }
 }
 
-#[derive(Component)]
-pub struct DamageTaken {
-    lifetime: Timer,
-}
-
-fn update(
+pub fn update(
     mut commands: Commands,
-    mut query: Query<(Entity, &mut Stats)>,
+    mut query: Query<(Entity, &mut Stats, Option<&EffectsController>, Has<Player>)>,
     transform_query: Query<&Transform, With<Stats>>,
-    mut damage_query: Query<(Entity, &mut DamageTaken, &mut Transform), Without<Stats>>,
     mut animation_controller_query: Query<&mut AnimationController>,
-    mob_query: Query<&Mob>,
+    mob_query: Query<(&Mob, &MobObject)>,
     mut money_event: EventWriter<DropMoneyEvent>,
+    mut death_event: EventWriter<PlayerDeathEvent>,
+    mut xp_event: EventWriter<XpEvent>,
     time: Res<Time>,
     asset_server: Res<AssetServer>,
+    mut rng: ResMut<WorldRng>,
 ) {
-    for (entity, mut stats) in query.iter_mut() {
-        if stats.health < stats.max_health {
-            let new_val = stats.health + stats.regen_rate * time.delta_seconds();
+    for (entity, mut stats, effects, is_player) in query.iter_mut() {
+        // Dying entities stay dead
+        if stats.health > 0. && stats.health < stats.max_health {
+            let regen_rate =
+                stats.regen_rate + effects.map_or(0., |effects| effects.total(Modifier::Regen));
+            let new_val = stats.health + regen_rate * time.delta_seconds();
             stats.health = if new_val > stats.max_health {
                 stats.max_health
             } else {
@@ -43,32 +45,6 @@ fn update(
         }
 
         if let Ok(transform) = transform_query.get(entity) {
-            if stats.taken_damage != 0.0 {
-                let mut taken_damage_transform = Transform::from_translation(transform.translation);
-                taken_damage_transform.translation.z += 1.0;
-                taken_damage_transform.rotate_z(random::<f32>() * PI / 4. - PI / 8.);
-
-                commands.spawn((
-                    DamageTaken {
-                        lifetime: Timer::from_seconds(1.0, TimerMode::Once),
-                    },
-                    Text2dBundle {
-                        text: Text::from_section(
-                            (-stats.taken_damage).to_string(),
-                            TextStyle {
-                                color: Color::RED,
-                                font_size: 15.,
-                                ..text_style(&asset_server)
-                            },
-                        ),
-                        transform: taken_damage_transform,
-                        ..Default::default()
-                    },
-                ));
-            }
-
-            stats.taken_damage = 0.;
-
             if stats.health <= 0. {
                 if let Ok(mut animation_controller) = animation_controller_query.get_mut(entity) {
                     if animation_controller
@@ -77,9 +53,15 @@ fn update(
                         .is_none()
                         || animation_controller.just_finished("Dead")
                     {
-                        if let Ok(mob) = mob_query.get(entity) {
+                        // The player respawns instead
+                        if is_player {
+                            death_event.send(PlayerDeathEvent);
+                            continue;
+                        }
+
+                        if let Ok((mob, object)) = mob_query.get(entity) {
                             let pos = transform.translation.xy();
-                            let (money, items) = mob.get_loot();
+                            let (money, items) = mob.get_loot(&mut *rng);
 
                             money_event.send(DropMoneyEvent {
                                 amount: money,
@@ -89,6 +71,10 @@ fn update(
                             items.into_iter().for_each(|loot| {
                                 commands.spawn(loot.bundle(&asset_server, pos));
                             });
+
+                            xp_event.send(XpEvent {
+                                amount: object.xp(),
+                            });
                         }
 
                         commands.entity(entity).despawn_recursive();
@@ -99,18 +85,10 @@ fn update(
             }
         }
     }
-
-    for (entity, mut damage_taken, mut transform) in damage_query.iter_mut() {
-        damage_taken.lifetime.tick(time.delta());
-        transform.translation.y += 50.0 * time.delta_seconds();
-
-        if damage_taken.lifetime.finished() {
-            commands.entity(entity).despawn();
-        }
-    }
 }
 
-#[derive(Component, Clone, Serialize, Deserialize)]
+#[derive(Component, Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(default)]
 pub struct Stats {
     pub strength: f32,
 
@@ -124,8 +102,7 @@ pub struct Stats {
 
     pub mass: f32,
 
-    #[serde(skip)]
-    taken_damage: f32,
+    pub resistances: Resistances,
 }
 
 impl Default for Stats {
@@ -138,7 +115,7 @@ impl Default for Stats {
             def: 0.0,
             speed: 300.0,
             mass: 300.0,
-            taken_damage: 0.,
+            resistances: Resistances::default(),
         }
     }
 }
@@ -150,17 +127,35 @@ impl Stats {
         self
     }
 
+    pub fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength;
+        self
+    }
+
     pub fn with_speed(mut self, speed: f32) -> Self {
         self.speed = speed;
         self
     }
+}
 
-    /// # Returns
-    /// The amount of damage actually taken accounting def and stuff
-    pub fn take_damage(&mut self, amount: f32) -> f32 {
-        let calc_amount = amount - self.def;
-        self.health -= calc_amount;
-        self.taken_damage = calc_amount;
-        calc_amount
+/// Part of the damage of each [`DamageType`] ignored, 1 is immune and a negative value a weakness
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(default)]
+pub struct Resistances {
+    pub physical: f32,
+    pub fire: f32,
+    pub poison: f32,
+    pub magic: f32,
+}
+
+impl Resistances {
+    pub fn get(&self, typ: DamageType) -> f32 {
+        match typ {
+            DamageType::Physical => self.physical,
+            DamageType::Fire => self.fire,
+            DamageType::Poison => self.poison,
+            DamageType::Magic => self.magic,
+        }
+        .min(1.)
     }
 }
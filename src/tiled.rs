@@ -3,17 +3,24 @@
 use crate::animation::{
     AnimatedSpriteBundle, Animation, AnimationController, AnimationDirection, AnimationMode,
 };
+use crate::boss::{list::BossObject, BossArena};
 use crate::chest::Chest;
 use crate::interactable::Interactable;
 use crate::items::loot_table::LootTable;
 use crate::lang::Lang;
 use crate::misc::read_img;
-use crate::mob::Mob;
+use crate::mob::{
+    definition::MobDefinitions,
+    list::MobObject,
+    spawn::{GroundSpots, SpawnZone},
+};
 use crate::npc::{Npc, NpcBundle, NpcTrait};
-use crate::ore::{MinableOreBundle, Ore};
-use crate::random::{RandomWeightedRate, RandomWeightedTable};
-use crate::save::CurrentSave;
-use crate::world::{World, BLOCK_SIZE};
+use crate::ore::{MinableOre, MinableOreBundle, Ore};
+use crate::player::death::Checkpoint;
+use crate::portal::{Portal, SpawnPoint};
+use crate::random::{RandomWeightedRate, RandomWeightedTable, WorldRng};
+use crate::save::{CurrentSave, MapObjectSave, WorldSave};
+use crate::world::{MobSpawnRate, World, BLOCK_SIZE};
 use bevy::asset::LoadContext;
 use bevy::sprite::Anchor;
 use bevy::{
@@ -26,10 +33,8 @@ use bevy_ecs_tilemap::prelude::*;
 use bevy_rapier2d::prelude::*;
 use bevy_rapier_collider_gen::single_polyline_collider_translated;
 use image::{DynamicImage, GenericImageView};
-use rand::prelude::SliceRandom;
-use rand::thread_rng;
 use std::f32::consts::PI;
-use std::io::{Cursor, ErrorKind};
+use std::io::Cursor;
 use std::panic::catch_unwind;
 use std::path::Path;
 use std::str::FromStr;
@@ -80,12 +85,8 @@ impl CollidingTileSet {
                 let mut tile_images: Vec<Handle<Image>> = Vec::new();
                 for (tile_id, tile) in tileset.tiles() {
                     if let Some(img) = &tile.image {
-                        let tmx_dir = load_context
-                            .path()
-                            .parent()
-                            .expect("The asset load context was empty.");
-                        let tile_path = tmx_dir.join(Path::new("../..").join(&img.source));
-                        let asset_path = AssetPath::from(tile_path);
+                        // Tiled resolves the sources from the folder of the map, whatever its depth
+                        let asset_path = AssetPath::from(img.source.clone());
                         let texture: Handle<Image> = load_context.load(asset_path.clone());
                         tile_image_offsets.insert((index, tile_id), tile_images.len() as u32);
                         tile_images.push(texture.clone());
@@ -103,12 +104,8 @@ impl CollidingTileSet {
                 }
             }
             Some(img) => {
-                let tmx_dir = load_context
-                    .path()
-                    .parent()
-                    .expect("The asset load context was empty.");
-                let tile_path = tmx_dir.join(Path::new("../..").join(&img.source));
-                let asset_path = AssetPath::from(tile_path);
+                // Tiled resolves the sources from the folder of the map, whatever its depth
+                let asset_path = AssetPath::from(img.source.clone());
                 let handle: Handle<Image> = load_context.load(asset_path.clone());
 
                 let img = read_img(asset_path);
@@ -238,7 +235,7 @@ fn img_is_slope(img: &DynamicImage) -> (bool, SlopeLayerDirection) {
 
             down_ratio >= upper_ratio - ERROR
         })
-        .reduce(|a, b| (a && b))
+        .reduce(|a, b| a && b)
         .unwrap();
 
     let direction = if is_slope {
@@ -354,9 +351,9 @@ impl AssetLoader for TiledLoader {
                 tiled::DefaultResourceCache::new(),
                 BytesResourceReader::new(&bytes),
             );
-            let map = loader.load_tmx_map(load_context.path()).map_err(|e| {
-                std::io::Error::new(ErrorKind::Other, format!("Could not load TMX map: {e}"))
-            })?;
+            let map = loader
+                .load_tmx_map(load_context.path())
+                .map_err(|e| std::io::Error::other(format!("Could not load TMX map: {e}")))?;
 
             let mut tilesets = HashMap::default();
             let mut tile_image_offsets = HashMap::default();
@@ -397,12 +394,12 @@ pub fn process_loaded_maps(
     maps: Res<Assets<TiledMap>>,
     tile_storage_query: Query<(Entity, &TileStorage)>,
     mut map_query: Query<(Entity, &Handle<TiledMap>, &World, &mut TiledLayersStorage)>,
-    children_query: Query<&Children, With<Handle<TiledMap>>>,
-    mut mob_transform_query: Query<&mut Transform, With<Mob>>,
     new_maps: Query<&Handle<TiledMap>, Added<Handle<TiledMap>>>,
     asset_server: Res<AssetServer>,
     lang: Res<Lang>,
     current_save: Res<CurrentSave>,
+    mob_definitions: Res<MobDefinitions>,
+    mut rng: ResMut<WorldRng>,
 ) {
     let mut changed_maps = Vec::<AssetId<TiledMap>>::default();
     for event in map_events.read() {
@@ -445,437 +442,738 @@ pub fn process_loaded_maps(
                     }
                 }
 
-                // The TilemapBundle requires that all tile images come exclusively from a single
-                // tiled texture or from a Vec of independent per-tile images. Furthermore, all of
-                // the per-tile images must be the same size. Since Tiled allows tiles of mixed
-                // tilesets on each layer and allows differently-sized tile images in each tileset,
-                // this means we need to load each combination of tileset and layer separately.
-
-                let map_size = TilemapSize {
-                    x: tiled_map.map.width,
-                    y: tiled_map.map.height,
+                let world_data = current_save.0.as_ref().unwrap().data.worlds.get(world);
+                let context = MapContext {
+                    entity,
+                    world,
+                    world_data,
+                    asset_server: &asset_server,
+                    lang: &lang,
+                    mob_definitions: &mob_definitions,
                 };
 
-                let grid_size = TilemapGridSize {
-                    x: tiled_map.map.tile_width as f32,
-                    y: tiled_map.map.tile_height as f32,
-                };
+                let mut ground_spots = Vec::new();
+                spawn_map(
+                    &mut commands,
+                    &context,
+                    tiled_map,
+                    &MapPlacement::centered(tiled_map),
+                    &mut layer_storage,
+                    &mut ground_spots,
+                    &mut rng,
+                );
 
-                let map_type = match tiled_map.map.orientation {
-                    tiled::Orientation::Hexagonal => TilemapType::Hexagon(HexCoordSystem::Row),
-                    tiled::Orientation::Isometric => {
-                        TilemapType::Isometric(IsoCoordSystem::Diamond)
-                    }
-                    tiled::Orientation::Staggered => {
-                        TilemapType::Isometric(IsoCoordSystem::Staggered)
-                    }
-                    tiled::Orientation::Orthogonal => TilemapType::Square,
+                commands
+                    .entity(entity)
+                    .insert((GroundSpots(ground_spots), Loaded));
+            }
+        }
+    }
+}
+
+/// What the objects of a map need to know about the world they are spawned in
+pub struct MapContext<'a> {
+    /// World entity the objects are children of
+    pub entity: Entity,
+    pub world: &'a World,
+    pub world_data: Option<&'a WorldSave>,
+    pub asset_server: &'a AssetServer,
+    pub lang: &'a Lang,
+    /// Mobs of the spawn zones are found by name
+    pub mob_definitions: &'a MobDefinitions,
+}
+
+/// Where a map is spawned, generated dungeons stitch several room maps in the same world
+pub struct MapPlacement {
+    /// Transform of the bottom left tile
+    pub transform: Transform,
+    /// Index of the room in a generated dungeon, keeps the ids and names of its objects unique
+    pub room: Option<u32>,
+    /// Tiles not spawned, in tile coordinates from the bottom left of the map
+    pub carved: Vec<IRect>,
+}
+
+impl MapPlacement {
+    /// Places the map centered on the origin of the world
+    pub fn centered(tiled_map: &TiledMap) -> Self {
+        let map_size = TilemapSize {
+            x: tiled_map.map.width,
+            y: tiled_map.map.height,
+        };
+        let grid_size = TilemapGridSize {
+            x: tiled_map.map.tile_width as f32,
+            y: tiled_map.map.tile_height as f32,
+        };
+
+        Self {
+            transform: get_tilemap_center_transform(
+                &map_size,
+                &grid_size,
+                &TilemapType::Square,
+                0.,
+            ),
+            room: None,
+            carved: Vec::new(),
+        }
+    }
+
+    /// # Returns
+    /// The id of a layer or an object, unique among every map of the world
+    pub fn id(&self, id: u32) -> u32 {
+        match self.room {
+            Some(room) => (room + 1) << 16 | id,
+            None => id,
+        }
+    }
+
+    /// # Returns
+    /// The name of an object, unique among every map of the world
+    pub fn name(&self, name: &str) -> String {
+        match self.room {
+            Some(room) => format!("{name} #{room}"),
+            None => name.to_owned(),
+        }
+    }
+
+    fn is_carved(&self, x: u32, y: u32) -> bool {
+        let pos = IVec2::new(x as i32, y as i32);
+        self.carved.iter().any(|rect| {
+            pos.x >= rect.min.x && pos.x < rect.max.x && pos.y >= rect.min.y && pos.y < rect.max.y
+        })
+    }
+}
+
+/// Spawns the tile layers, colliders and objects of a Tiled map into the world `context.entity`
+pub fn spawn_map(
+    commands: &mut Commands,
+    context: &MapContext,
+    tiled_map: &TiledMap,
+    placement: &MapPlacement,
+    layer_storage: &mut TiledLayersStorage,
+    ground_spots: &mut Vec<Vec2>,
+    rng: &mut WorldRng,
+) {
+    let MapContext {
+        entity,
+        world,
+        world_data,
+        asset_server,
+        lang,
+        mob_definitions,
+    } = *context;
+
+    // The TilemapBundle requires that all tile images come exclusively from a single
+    // tiled texture or from a Vec of independent per-tile images. Furthermore, all of
+    // the per-tile images must be the same size. Since Tiled allows tiles of mixed
+    // tilesets on each layer and allows differently-sized tile images in each tileset,
+    // this means we need to load each combination of tileset and layer separately.
+
+    let map_size = TilemapSize {
+        x: tiled_map.map.width,
+        y: tiled_map.map.height,
+    };
+
+    let grid_size = TilemapGridSize {
+        x: tiled_map.map.tile_width as f32,
+        y: tiled_map.map.tile_height as f32,
+    };
+
+    let map_type = match tiled_map.map.orientation {
+        tiled::Orientation::Hexagonal => TilemapType::Hexagon(HexCoordSystem::Row),
+        tiled::Orientation::Isometric => TilemapType::Isometric(IsoCoordSystem::Diamond),
+        tiled::Orientation::Staggered => TilemapType::Isometric(IsoCoordSystem::Staggered),
+        tiled::Orientation::Orthogonal => TilemapType::Square,
+    };
+
+    for (layer_index, layer) in tiled_map.map.layers().enumerate() {
+        let offset_x = layer.offset_x;
+        let offset_y = layer.offset_y;
+
+        let layer_offset =
+            placement.transform * Transform::from_xyz(offset_x, -offset_y, -(layer.id() as f32));
+
+        let no_hitbox = layer.properties.get("no_hitbox").is_some_and(|prop| {
+            if let PropertyValue::BoolValue(b) = prop {
+                *b
+            } else {
+                false
+            }
+        });
+
+        match layer.layer_type() {
+            tiled::LayerType::Tiles(tile_layer) => {
+                let tiled::TileLayer::Finite(layer_data) = tile_layer else {
+                    log::info!(
+                        "Skipping layer {} because only finite layers are supported.",
+                        layer.id()
+                    );
+                    continue;
                 };
 
-                let tile_map_offset =
-                    get_tilemap_center_transform(&map_size, &grid_size, &map_type, 0.);
+                for (tileset_index, tileset) in tiled_map.map.tilesets().iter().enumerate() {
+                    let Some(colliding_tileset) = tiled_map.tilesets.get(&tileset_index) else {
+                        log::warn!("Skipped creating layer with missing tilemap textures.");
+                        continue;
+                    };
 
-                let world_data = current_save.0.as_ref().unwrap().data.worlds.get(world);
+                    let tile_size = TilemapTileSize {
+                        x: tileset.tile_width as f32,
+                        y: tileset.tile_height as f32,
+                    };
 
-                for (layer_index, layer) in tiled_map.map.layers().enumerate() {
-                    let offset_x = layer.offset_x;
-                    let offset_y = layer.offset_y;
+                    let tile_spacing = TilemapSpacing {
+                        x: tileset.spacing as f32,
+                        y: tileset.spacing as f32,
+                    };
 
-                    let layer_offset = tile_map_offset
-                        * Transform::from_xyz(offset_x, -offset_y, -(layer.id() as f32));
+                    let mut tile_storage = TileStorage::empty(map_size);
+                    let layer_entity = commands.spawn_empty().id();
 
-                    let no_hitbox = layer.properties.get("no_hitbox").is_some_and(|prop| {
-                        if let PropertyValue::BoolValue(b) = prop {
-                            *b
-                        } else {
-                            false
-                        }
-                    });
+                    let mut tiles_refs = Vec::new();
 
-                    match layer.layer_type() {
-                        tiled::LayerType::Tiles(tile_layer) => {
-                            let tiled::TileLayer::Finite(layer_data) = tile_layer else {
-                                log::info!(
-                                    "Skipping layer {} because only finite layers are supported.",
-                                    layer.id()
-                                );
+                    for x in 0..map_size.x {
+                        for y in 0..map_size.y {
+                            if placement.is_carved(x, y) {
                                 continue;
-                            };
+                            }
+
+                            // Transform TMX coords into bevy coords.
+                            let mapped_y = tiled_map.map.height - 1 - y;
+
+                            let mapped_x = x as i32;
+                            let mapped_y = mapped_y as i32;
 
-                            for (tileset_index, tileset) in
-                                tiled_map.map.tilesets().iter().enumerate()
+                            let layer_tile = match layer_data.get_tile(mapped_x, mapped_y) {
+                                Some(t) => t,
+                                None => {
+                                    continue;
+                                }
+                            };
+                            if tileset_index != layer_tile.tileset_index() {
+                                continue;
+                            }
+                            let layer_tile_data = match layer_data.get_tile_data(mapped_x, mapped_y)
                             {
-                                let Some(colliding_tileset) =
-                                    tiled_map.tilesets.get(&tileset_index)
-                                else {
-                                    log::warn!(
-                                        "Skipped creating layer with missing tilemap textures."
-                                    );
+                                Some(d) => d,
+                                None => {
                                     continue;
-                                };
-
-                                let tile_size = TilemapTileSize {
-                                    x: tileset.tile_width as f32,
-                                    y: tileset.tile_height as f32,
-                                };
-
-                                let tile_spacing = TilemapSpacing {
-                                    x: tileset.spacing as f32,
-                                    y: tileset.spacing as f32,
-                                };
-
-                                let mut tile_storage = TileStorage::empty(map_size);
-                                let layer_entity = commands.spawn_empty().id();
-
-                                let mut tiles_refs = Vec::new();
-
-                                for x in 0..map_size.x {
-                                    for y in 0..map_size.y {
-                                        // Transform TMX coords into bevy coords.
-                                        let mapped_y = tiled_map.map.height - 1 - y;
-
-                                        let mapped_x = x as i32;
-                                        let mapped_y = mapped_y as i32;
-
-                                        let layer_tile =
-                                            match layer_data.get_tile(mapped_x, mapped_y) {
-                                                Some(t) => t,
-                                                None => {
-                                                    continue;
-                                                }
-                                            };
-                                        if tileset_index != layer_tile.tileset_index() {
-                                            continue;
-                                        }
-                                        let layer_tile_data =
-                                            match layer_data.get_tile_data(mapped_x, mapped_y) {
-                                                Some(d) => d,
-                                                None => {
-                                                    continue;
-                                                }
-                                            };
-
-                                        let texture_index = match colliding_tileset.texture {
-                                    TilemapTexture::Single(_) => layer_tile.id(),
-                                    #[cfg(not(feature = "atlas"))]
-                                    TilemapTexture::Vector(_) =>
-                                        *tiled_map.tile_image_offsets.get(&(tileset_index, layer_tile.id()))
-                                        .expect("The offset into to image vector should have been saved during the initial load."),
-                                    #[cfg(not(feature = "atlas"))]
-                                    _ => unreachable!()
-                                };
-
-                                        let tile_pos = TilePos { x, y };
-                                        let tile_bundle = TileBundle {
-                                            position: tile_pos,
-                                            tilemap_id: TilemapId(layer_entity),
-                                            texture_index: TileTextureIndex(texture_index),
-                                            flip: TileFlip {
-                                                x: layer_tile_data.flip_h,
-                                                y: layer_tile_data.flip_v,
-                                                d: layer_tile_data.flip_d,
-                                            },
-                                            ..Default::default()
-                                        };
+                                }
+                            };
 
-                                        let collider = colliding_tileset
-                                            .colliders
-                                            .get(&layer_tile.id())
-                                            .expect("hitbox not found")
-                                            .clone();
+                            let texture_index = match colliding_tileset.texture {
+                        TilemapTexture::Single(_) => layer_tile.id(),
+                        #[cfg(not(feature = "atlas"))]
+                        TilemapTexture::Vector(_) =>
+                            *tiled_map.tile_image_offsets.get(&(tileset_index, layer_tile.id()))
+                            .expect("The offset into to image vector should have been saved during the initial load."),
+                        #[cfg(not(feature = "atlas"))]
+                        _ => unreachable!()
+                    };
+
+                            let tile_pos = TilePos { x, y };
+                            let tile_bundle = TileBundle {
+                                position: tile_pos,
+                                tilemap_id: TilemapId(layer_entity),
+                                texture_index: TileTextureIndex(texture_index),
+                                flip: TileFlip {
+                                    x: layer_tile_data.flip_h,
+                                    y: layer_tile_data.flip_v,
+                                    d: layer_tile_data.flip_d,
+                                },
+                                ..Default::default()
+                            };
 
-                                        let mut tile_transform = layer_offset;
+                            let collider = colliding_tileset
+                                .colliders
+                                .get(&layer_tile.id())
+                                .expect("hitbox not found")
+                                .clone();
 
-                                        if layer_tile_data.flip_h {
-                                            tile_transform.scale.x *= -1.;
-                                        }
-                                        if layer_tile_data.flip_v {
-                                            tile_transform.scale.y *= -1.;
-                                        }
+                            let mut tile_transform = layer_offset;
 
-                                        tile_transform.translation += tile_pos
-                                            .center_in_world(&grid_size, &map_type)
-                                            .extend(0.0);
-
-                                        let mut cmd = commands.spawn(tile_bundle);
-
-                                        if !no_hitbox {
-                                            if let Some(c) = collider {
-                                                tiles_refs.push((
-                                                    tile_pos,
-                                                    tile_transform,
-                                                    c.clone(),
-                                                ));
-                                                cmd.insert((
-                                                    c,
-                                                    TransformBundle::from(tile_transform),
-                                                ));
-                                            }
-                                        }
+                            if layer_tile_data.flip_h {
+                                tile_transform.scale.x *= -1.;
+                            }
+                            if layer_tile_data.flip_v {
+                                tile_transform.scale.y *= -1.;
+                            }
 
-                                        let tile_entity = cmd.id();
-                                        tile_storage.set(&tile_pos, tile_entity);
-                                    }
-                                }
+                            tile_transform.translation +=
+                                tile_pos.center_in_world(&grid_size, &map_type).extend(0.0);
 
-                                // Move mobs
-                                let available_mob_spawn_spots = tiles_refs
-                                    .iter()
-                                    .filter_map(|(tile_pos, transform, collider)| {
-                                        let upper_tile_pos =
-                                            TilePos::new(tile_pos.x, tile_pos.y + 1);
-                                        let upper_tile = tiles_refs
-                                            .iter()
-                                            .find(|(pos, _, _)| *pos == upper_tile_pos);
-
-                                        collider.as_cuboid().and_then(|_| {
-                                            if upper_tile.is_none() {
-                                                Some(transform.translation.xy())
-                                            } else {
-                                                None
-                                            }
-                                        })
-                                    })
-                                    .collect::<Vec<_>>();
-
-                                if let Ok(children) = children_query.get(entity) {
-                                    for child in children {
-                                        if let Ok(mut transform) =
-                                            mob_transform_query.get_mut(*child)
-                                        {
-                                            commands.entity(entity).remove_children(&[*child]);
-                                            let z = transform.translation.z;
-
-                                            let spot =
-                                                available_mob_spawn_spots.choose(&mut thread_rng());
-                                            if let Some(vec2) = spot {
-                                                let mut v = *vec2;
-                                                v.y += BLOCK_SIZE;
-                                                transform.translation = v.extend(z);
-                                            }
-                                        }
-                                    }
-                                };
-
-                                commands.entity(layer_entity).insert(TilemapBundle {
-                                    grid_size,
-                                    size: map_size,
-                                    storage: tile_storage,
-                                    texture: colliding_tileset.texture.clone(),
-                                    tile_size,
-                                    spacing: tile_spacing,
-                                    transform: layer_offset,
-                                    map_type,
-                                    ..Default::default()
-                                });
+                            let mut cmd = commands.spawn(tile_bundle);
 
-                                layer_storage
-                                    .storage
-                                    .insert(layer_index as u32, layer_entity);
+                            if !no_hitbox {
+                                if let Some(c) = collider {
+                                    tiles_refs.push((tile_pos, tile_transform, c.clone()));
+                                    cmd.insert((c, TransformBundle::from(tile_transform)));
+                                }
                             }
+
+                            let tile_entity = cmd.id();
+                            tile_storage.set(&tile_pos, tile_entity);
                         }
+                    }
 
-                        tiled::LayerType::Objects(object_layer) => {
-                            for object in object_layer.objects() {
-                                let mut entity_commands = commands.spawn_empty();
-
-                                let mut transform = Transform::from_translation(
-                                    layer_offset.translation
-                                        + Vec3::new(
-                                            object.x,
-                                            (map_size.y as f32 * grid_size.y) - object.y,
-                                            0.0,
-                                        ),
-                                );
+                    // Where mobs can stand
+                    ground_spots.extend(tiles_refs.iter().filter_map(
+                        |(tile_pos, transform, collider)| {
+                            let upper_tile_pos = TilePos::new(tile_pos.x, tile_pos.y + 1);
+                            let upper_tile =
+                                tiles_refs.iter().find(|(pos, _, _)| *pos == upper_tile_pos);
+
+                            collider.as_cuboid().and_then(|_| {
+                                if upper_tile.is_none() {
+                                    Some(transform.translation.xy())
+                                } else {
+                                    None
+                                }
+                            })
+                        },
+                    ));
+
+                    commands.entity(layer_entity).insert(TilemapBundle {
+                        grid_size,
+                        size: map_size,
+                        storage: tile_storage,
+                        texture: colliding_tileset.texture.clone(),
+                        tile_size,
+                        spacing: tile_spacing,
+                        transform: layer_offset,
+                        map_type,
+                        ..Default::default()
+                    });
 
-                                match object.user_type.as_str() {
-                                    "Chest" => {
-                                        if world_data.is_some_and(|data| {
-                                            data.available_chests.as_ref().is_some_and(
-                                                |available_chests| {
-                                                    !available_chests.contains(&object.name)
-                                                },
-                                            )
-                                        }) {
-                                            entity_commands.despawn();
-                                            continue;
-                                        }
+                    layer_storage
+                        .storage
+                        .insert(placement.id(layer_index as u32), layer_entity);
+                }
+            }
 
-                                        if let Some(loot_table) = object
-                                            .properties
-                                            .get("loot_table")
-                                            .and_then(|loot_table_prop| match loot_table_prop {
-                                                PropertyValue::StringValue(s) => Some(s),
-                                                _ => None,
-                                            })
-                                            .and_then(|loot_table_name| {
-                                                LootTable::read(
-                                                    &Path::new("chest")
-                                                        .join(format!("{loot_table_name}.json")),
-                                                )
-                                            })
-                                        {
-                                            if let Some(chest_type) = object
-                                                .properties
-                                                .get("chest_type")
-                                                .and_then(|prop| match prop {
-                                                    PropertyValue::IntValue(c) => Some(c),
-                                                    _ => None,
-                                                })
-                                            {
-                                                let chest_type_str = chest_type.to_string();
-                                                let mut animations = HashMap::new();
-                                                for i in 1..=4 {
-                                                    animations.insert(
-                                                        i.to_string(),
-                                                        Animation::new(
-                                                            format!("textures/chest/{i}.png"),
-                                                            &asset_server,
-                                                            Duration::from_secs_f32(1.0),
-                                                            64,
-                                                            AnimationMode::Custom,
-                                                            AnimationDirection::Forwards,
-                                                        ),
-                                                    );
-                                                }
-
-                                                transform.translation.y += BLOCK_SIZE / 2.;
-
-                                                let mut animation_controller =
-                                                    AnimationController::new(animations);
-                                                animation_controller.play(&chest_type_str);
-
-                                                entity_commands.insert((
-                                                    Interactable::new("player.actions.open"),
-                                                    Chest {
-                                                        name: object.name.clone(),
-                                                        loot_table,
-                                                        chest_type: *chest_type,
-                                                    },
-                                                    AnimatedSpriteBundle {
-                                                        sprite: SpriteSheetBundle {
-                                                            sprite: TextureAtlasSprite {
-                                                                anchor: Anchor::Custom(Vec2::new(
-                                                                    0., -0.25,
-                                                                )),
-                                                                ..Default::default()
-                                                            },
-                                                            transform,
-                                                            ..Default::default()
-                                                        },
-                                                        animation_controller,
-                                                    },
-                                                    Collider::cuboid(16., 16.),
-                                                ));
-                                            }
-                                        }
-                                    }
-                                    "NPC" => {
-                                        let npc =
-                                            Npc::from_str(&object.name).expect("npc not found");
-
-                                        let animation = Animation::new(
-                                            npc.get_texture(),
-                                            &asset_server,
-                                            Duration::from_secs_f32(1.5),
-                                            npc.texture_size(),
-                                            AnimationMode::Repeating,
-                                            AnimationDirection::Forwards,
-                                        );
+            tiled::LayerType::Objects(object_layer) => {
+                for object in object_layer.objects() {
+                    let mut entity_commands = commands.spawn_empty();
+
+                    let mut transform = Transform::from_translation(
+                        layer_offset.translation
+                            + Vec3::new(
+                                object.x,
+                                (map_size.y as f32 * grid_size.y) - object.y,
+                                0.0,
+                            ),
+                    );
 
-                                        transform.translation.y -= BLOCK_SIZE / 2.;
+                    match object.user_type.as_str() {
+                        "Chest" => {
+                            let name = placement.name(&object.name);
 
-                                        let mut animations = HashMap::new();
-                                        animations.insert("Idle".into(), animation);
+                            if world_data.is_some_and(|data| {
+                                data.available_chests
+                                    .as_ref()
+                                    .is_some_and(|available_chests| {
+                                        !available_chests.contains(&name)
+                                    })
+                            }) {
+                                entity_commands.despawn();
+                                continue;
+                            }
 
-                                        entity_commands.insert(NpcBundle {
-                                            npc,
-                                            interactable: Interactable::new(
-                                                lang.get("player.actions.talk"),
+                            if let Some(loot_table) = object
+                                .properties
+                                .get("loot_table")
+                                .and_then(|loot_table_prop| match loot_table_prop {
+                                    PropertyValue::StringValue(s) => Some(s),
+                                    _ => None,
+                                })
+                                .and_then(|loot_table_name| {
+                                    LootTable::read(
+                                        &Path::new("chest").join(format!("{loot_table_name}.json")),
+                                    )
+                                })
+                            {
+                                if let Some(chest_type) = object
+                                    .properties
+                                    .get("chest_type")
+                                    .and_then(|prop| match prop {
+                                        PropertyValue::IntValue(c) => Some(c),
+                                        _ => None,
+                                    })
+                                {
+                                    let chest_type_str = chest_type.to_string();
+                                    let mut animations = HashMap::new();
+                                    for i in 1..=4 {
+                                        animations.insert(
+                                            i.to_string(),
+                                            Animation::new(
+                                                format!("textures/chest/{i}.png"),
+                                                asset_server,
+                                                Duration::from_secs_f32(1.0),
+                                                64,
+                                                AnimationMode::Custom,
+                                                AnimationDirection::Forwards,
                                             ),
-                                            sprite: AnimatedSpriteBundle {
-                                                animation_controller: AnimationController::new(
-                                                    animations,
-                                                )
-                                                .with_default("Idle"),
-                                                sprite: SpriteSheetBundle {
-                                                    sprite: TextureAtlasSprite {
-                                                        anchor: Anchor::BottomCenter,
-                                                        ..Default::default()
-                                                    },
-                                                    transform,
+                                        );
+                                    }
+
+                                    transform.translation.y += BLOCK_SIZE / 2.;
+
+                                    let mut animation_controller =
+                                        AnimationController::new(animations);
+                                    animation_controller.play(&chest_type_str);
+
+                                    entity_commands.insert((
+                                        Interactable::new("player.actions.open"),
+                                        Chest {
+                                            name,
+                                            loot_table,
+                                            chest_type: *chest_type,
+                                        },
+                                        AnimatedSpriteBundle {
+                                            sprite: SpriteSheetBundle {
+                                                sprite: TextureAtlasSprite {
+                                                    anchor: Anchor::Custom(Vec2::new(0., -0.25)),
                                                     ..Default::default()
                                                 },
+                                                transform,
+                                                ..Default::default()
                                             },
-                                        });
+                                            animation_controller,
+                                        },
+                                        Collider::cuboid(16., 16.),
+                                    ));
+                                }
+                            }
+                        }
+                        "NPC" => {
+                            let npc = Npc::from_str(&object.name).expect("npc not found");
+
+                            let animation = Animation::new(
+                                npc.get_texture(),
+                                asset_server,
+                                Duration::from_secs_f32(1.5),
+                                npc.texture_size(),
+                                AnimationMode::Repeating,
+                                AnimationDirection::Forwards,
+                            );
+
+                            transform.translation.y -= BLOCK_SIZE / 2.;
+
+                            let mut animations = HashMap::new();
+                            animations.insert("Idle".into(), animation);
+
+                            entity_commands.insert(NpcBundle {
+                                npc,
+                                interactable: Interactable::new(lang.get("player.actions.talk")),
+                                sprite: AnimatedSpriteBundle {
+                                    animation_controller: AnimationController::new(animations)
+                                        .with_default("Idle"),
+                                    sprite: SpriteSheetBundle {
+                                        sprite: TextureAtlasSprite {
+                                            anchor: Anchor::BottomCenter,
+                                            ..Default::default()
+                                        },
+                                        transform,
+                                        ..Default::default()
+                                    },
+                                },
+                            });
+                        }
+
+                        "Ore" => {
+                            let regrow_time = object
+                                .properties
+                                .get("regrow_time")
+                                .and_then(|prop| {
+                                    let regrow_time = match prop {
+                                        PropertyValue::IntValue(secs) => {
+                                            u64::try_from(*secs).ok().map(Duration::from_secs)
+                                        }
+                                        PropertyValue::FloatValue(secs) => {
+                                            Duration::try_from_secs_f32(*secs).ok()
+                                        }
+                                        _ => None,
                                     }
+                                    .filter(|regrow_time| !regrow_time.is_zero());
 
-                                    "Ore" => {
-                                        let rates: Vec<RandomWeightedRate<Ore>> = object
-                                            .properties
-                                            .iter()
-                                            .filter_map(|(ore_name, weight_value)| {
-                                                let weight = match weight_value {
-                                                    PropertyValue::IntValue(int) => {
-                                                        Some(*int as u32)
-                                                    }
-                                                    _ => {
-                                                        error!("Please specify an int type for ore weight, given");
-                                                        None
-                                                    }
-                                                }?;
-
-                                                let ore = Ore::from_str(&ore_name)
-                                                    .map_err(|_| {
-                                                        error!("Ore type {ore_name} not found");
-                                                    })
-                                                    .ok()?;
-
-                                                Some(RandomWeightedRate { data: ore, weight })
+                                    if regrow_time.is_none() {
+                                        warn!(
+                                            "Ore {} needs a positive regrow_time, using the default",
+                                            object.name
+                                        );
+                                    }
+                                    regrow_time
+                                })
+                                .unwrap_or(MinableOre::DEFAULT_REGROW_TIME);
+
+                            let rates: Vec<RandomWeightedRate<Ore>> = object
+                                .properties
+                                .iter()
+                                .filter(|(name, _)| *name != "regrow_time")
+                                .filter_map(|(ore_name, weight_value)| {
+                                    let weight = match weight_value {
+                                        PropertyValue::IntValue(int) => u32::try_from(*int)
+                                            .map_err(|_| {
+                                                warn!("Ore {ore_name} has a negative weight");
                                             })
-                                            .collect();
-
-                                        if rates.is_empty() {
-                                            error!("Ore {} is empty", object.name);
-                                            continue;
+                                            .ok(),
+                                        _ => {
+                                            error!(
+                                                "Please specify an int type for ore weight, given"
+                                            );
+                                            None
                                         }
+                                    }?;
 
-                                        let table = RandomWeightedTable::new(1, rates);
+                                    let ore = Ore::from_str(ore_name)
+                                        .map_err(|_| {
+                                            error!("Ore type {ore_name} not found");
+                                        })
+                                        .ok()?;
+
+                                    Some(RandomWeightedRate { data: ore, weight })
+                                })
+                                .collect();
 
-                                        entity_commands.insert(MinableOreBundle::new(
-                                            table,
-                                            transform.translation.xy(),
-                                            object.rotation.to_radians() + 2. * PI,
-                                            &asset_server,
-                                        ));
+                            if rates.is_empty() {
+                                error!("Ore {} is empty", object.name);
+                                continue;
+                            }
+
+                            let table = RandomWeightedTable::new(1, rates);
+
+                            let saved_state = world_data
+                                .and_then(|data| data.objects.get(&placement.id(object.id())));
+
+                            let (current_ore, regrow_at) = match saved_state {
+                                Some(MapObjectSave::Ore {
+                                    current_ore,
+                                    regrow_at,
+                                }) => (current_ore.clone(), *regrow_at),
+                                None => (table.get_random(rng).first().unwrap().clone(), None),
+                            };
+
+                            entity_commands.insert(MinableOreBundle::new(
+                                MinableOre {
+                                    current_ore,
+                                    random_ore_table: table,
+                                    object_id: placement.id(object.id()),
+                                    regrow_time,
+                                    regrow_at,
+                                },
+                                transform.translation.xy(),
+                                object.rotation.to_radians() + 2. * PI,
+                                asset_server,
+                            ));
+                        }
+
+                        "Portal" | "Door" => {
+                            let target =
+                                object.properties.get("world").and_then(|prop| match prop {
+                                    PropertyValue::StringValue(s) => World::from_str(s).ok(),
+                                    _ => None,
+                                });
+
+                            let Some(target) = target else {
+                                error!(
+                                    "{} {} has no valid target world",
+                                    object.user_type, object.name
+                                );
+                                entity_commands.despawn();
+                                continue;
+                            };
+
+                            let spawn_point = match object.properties.get("spawn") {
+                                Some(PropertyValue::StringValue(s)) => s.clone(),
+                                _ => String::new(),
+                            };
+
+                            entity_commands.insert((
+                                Portal {
+                                    target,
+                                    spawn_point,
+                                },
+                                Interactable::new("player.actions.enter"),
+                                SpatialBundle::from_transform(transform),
+                            ));
+                        }
+
+                        "MobSpawn" => {
+                            let ObjectShape::Rect { width, height } = object.shape else {
+                                error!("Mob spawn {} must be a rectangle", object.name);
+                                entity_commands.despawn();
+                                continue;
+                            };
+
+                            let rates: Vec<RandomWeightedRate<MobSpawnRate>> = object
+                                .properties
+                                .iter()
+                                .filter_map(|(name, value)| {
+                                    let PropertyValue::IntValue(weight) = value else {
+                                        return None;
+                                    };
+                                    let mob = MobObject::from_name(name, mob_definitions)?;
+                                    let Ok(weight) = u32::try_from(*weight) else {
+                                        warn!(
+                                            "Mob {name} of spawn {} has a negative weight",
+                                            object.name
+                                        );
+                                        return None;
+                                    };
+
+                                    Some(RandomWeightedRate {
+                                        data: MobSpawnRate { mob, group: 1..=1 },
+                                        weight,
+                                    })
+                                })
+                                .collect();
+
+                            // Zones without mobs use the spawn rates of the biome
+                            let spawn_rates = if !rates.is_empty() {
+                                Some(RandomWeightedTable::new(1, rates))
+                            } else if let World::Biome(_) = world {
+                                None
+                            } else {
+                                error!("Mob spawn {} has no mobs", object.name);
+                                entity_commands.despawn();
+                                continue;
+                            };
+
+                            let max_population = match object.properties.get("max_population") {
+                                Some(PropertyValue::IntValue(max)) => usize::try_from(*max)
+                                    .unwrap_or_else(|_| {
+                                        warn!(
+                                            "Mob spawn {} has a negative max_population, using the default",
+                                            object.name
+                                        );
+                                        SpawnZone::DEFAULT_MAX_POPULATION
+                                    }),
+                                _ => SpawnZone::DEFAULT_MAX_POPULATION,
+                            };
+
+                            let respawn_time = object
+                                .properties
+                                .get("respawn_time")
+                                .and_then(|prop| {
+                                    let respawn_time = match prop {
+                                        PropertyValue::IntValue(secs) => {
+                                            u64::try_from(*secs).ok().map(Duration::from_secs)
+                                        }
+                                        PropertyValue::FloatValue(secs) => {
+                                            Duration::try_from_secs_f32(*secs).ok()
+                                        }
+                                        _ => None,
                                     }
+                                    .filter(|respawn_time| !respawn_time.is_zero());
 
-                                    _ => {
-                                        // if let Some(collider) =
-                                        //     collider_from_object_shape(&object.shape)
-                                        // {
-                                        //     entity_commands.insert(collider);
-                                        // }
+                                    if respawn_time.is_none() {
+                                        warn!(
+                                            "Mob spawn {} needs a positive respawn_time, using the default",
+                                            object.name
+                                        );
                                     }
-                                }
+                                    respawn_time
+                                })
+                                .unwrap_or(SpawnZone::DEFAULT_RESPAWN_TIME);
+
+                            let top_left = transform.translation.xy();
+
+                            entity_commands.insert(SpawnZone {
+                                rect: Rect::new(
+                                    top_left.x,
+                                    top_left.y - height,
+                                    top_left.x + width,
+                                    top_left.y,
+                                ),
+                                spawn_rates,
+                                max_population,
+                                respawn_timer: Timer::new(respawn_time, TimerMode::Repeating),
+                                populated: world_data.is_some(),
+                            });
+                        }
+
+                        "Boss" => {
+                            let ObjectShape::Rect { width, height } = object.shape else {
+                                error!("Boss arena {} must be a rectangle", object.name);
+                                entity_commands.despawn();
+                                continue;
+                            };
 
-                                let object_entity = entity_commands.id();
+                            let boss = match object.properties.get("boss") {
+                                Some(PropertyValue::StringValue(s)) => BossObject::from_str(s).ok(),
+                                _ => None,
+                            };
 
-                                commands.entity(entity).add_child(object_entity);
-                            }
+                            let Some(boss) = boss else {
+                                error!("Boss arena {} has no valid boss", object.name);
+                                entity_commands.despawn();
+                                continue;
+                            };
+
+                            let top_left = transform.translation.xy();
+                            let rect = Rect::new(
+                                top_left.x,
+                                top_left.y - height,
+                                top_left.x + width,
+                                top_left.y,
+                            );
+
+                            entity_commands.insert((
+                                BossArena::new(boss, rect),
+                                SpatialBundle::from_transform(Transform::from_translation(
+                                    rect.center().extend(transform.translation.z),
+                                )),
+                            ));
                         }
 
-                        _ => {
-                            log::info!(
-                                    "Skipping layer {} because only tile and object layers are supported.",
-                                    layer.id()
-                                );
+                        // Only used to generate dungeons
+                        "Connector" => {
+                            entity_commands.despawn();
                             continue;
                         }
+
+                        "Spawn" => {
+                            entity_commands.insert((
+                                SpawnPoint {
+                                    name: object.name.clone(),
+                                },
+                                TransformBundle::from_transform(transform),
+                            ));
+                        }
+
+                        "Checkpoint" => {
+                            entity_commands.insert((
+                                Checkpoint {
+                                    name: object.name.clone(),
+                                },
+                                SpawnPoint {
+                                    name: object.name.clone(),
+                                },
+                                Interactable::new("player.actions.rest"),
+                                SpatialBundle::from_transform(transform),
+                            ));
+                        }
+
+                        _ => {
+                            // if let Some(collider) =
+                            //     collider_from_object_shape(&object.shape)
+                            // {
+                            //     entity_commands.insert(collider);
+                            // }
+                        }
                     }
+
+                    let object_entity = entity_commands.id();
+
+                    commands.entity(entity).add_child(object_entity);
                 }
+            }
 
-                commands.entity(entity).insert(Loaded);
+            _ => {
+                log::info!(
+                    "Skipping layer {} because only tile and object layers are supported.",
+                    layer.id()
+                );
+                continue;
             }
         }
     }
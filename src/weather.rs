@@ -0,0 +1,338 @@
+use crate::{
+    gui::main_menu::MainMenuState,
+    random::RandomWeightedTable,
+    state::AppState,
+    world::{is_loading, World},
+};
+use bevy::prelude::*;
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+pub struct WeatherPlugin;
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WeatherParticleTimer(Timer::from_seconds(
+            WeatherParticleTimer::SECONDS,
+            TimerMode::Repeating,
+        )))
+        .add_systems(
+            Update,
+            (
+                tick_weather.run_if(not(is_loading)),
+                update_weather_overlay,
+                spawn_weather_particles,
+                update_weather_particles,
+            )
+                .chain()
+                .run_if(in_state(AppState::InGame)),
+        )
+        .add_systems(
+            OnEnter(AppState::MainMenu(MainMenuState::Default)),
+            despawn_weather,
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Rain,
+    Sandstorm,
+    Fog,
+}
+
+pub type WeatherRates = RandomWeightedTable<Weather>;
+
+impl Weather {
+    /// Multiplier applied to the [`Stats::speed`](crate::stats::Stats::speed) of everything walking outside
+    pub fn speed_multiplier(&self) -> f32 {
+        match self {
+            Self::Sandstorm => 0.6,
+            Self::Rain => 0.9,
+            Self::Clear | Self::Fog => 1.,
+        }
+    }
+
+    /// # Returns
+    /// How much of the world can be seen, from 0 to 1 for a clear sky
+    pub fn visibility(&self) -> f32 {
+        match self {
+            Self::Clear => 1.,
+            Self::Rain => 0.85,
+            Self::Sandstorm => 0.55,
+            Self::Fog => 0.4,
+        }
+    }
+
+    /// Color of the screen wide overlay, its alpha hides what can't be seen
+    fn overlay_color(&self) -> Color {
+        let alpha = 1. - self.visibility();
+        match self {
+            Self::Clear => Color::NONE,
+            Self::Rain => Color::rgba(0.2, 0.25, 0.35, alpha),
+            Self::Sandstorm => Color::rgba(0.8, 0.6, 0.3, alpha),
+            Self::Fog => Color::rgba(0.75, 0.78, 0.8, alpha),
+        }
+    }
+
+    fn particles(&self) -> Option<WeatherParticles> {
+        match self {
+            Self::Rain => Some(WeatherParticles {
+                count: 4,
+                size: Vec2::new(1.5, 10.),
+                color: Color::rgba(0.6, 0.7, 1., 0.6),
+                velocity: Vec2::new(-60., -700.),
+                from_side: false,
+            }),
+            Self::Sandstorm => Some(WeatherParticles {
+                count: 6,
+                size: Vec2::new(3., 2.),
+                color: Color::rgba(0.85, 0.7, 0.45, 0.8),
+                velocity: Vec2::new(500., -40.),
+                from_side: true,
+            }),
+            Self::Clear | Self::Fog => None,
+        }
+    }
+}
+
+impl FromStr for Weather {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clear" => Ok(Self::Clear),
+            "rain" => Ok(Self::Rain),
+            "sandstorm" => Ok(Self::Sandstorm),
+            "fog" => Ok(Self::Fog),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Weather of a [`World`], saved with its [`WorldSave`](crate::save::WorldSave)
+///
+/// The weather changes every [`WorldWeather::PERIOD_DURATION`] seconds, the weather of each period
+/// only depends on the seed so the schedule is the same every time the world is played
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct WorldWeather {
+    pub seed: u64,
+    pub period: u64,
+    pub current: Weather,
+    /// Seconds before the next period
+    pub remaining: f32,
+}
+
+impl WorldWeather {
+    pub const PERIOD_DURATION: f32 = 240.;
+
+    pub fn new(seed: u64, rates: &WeatherRates) -> Self {
+        Self {
+            seed,
+            period: 0,
+            current: Self::roll(seed, 0, rates),
+            remaining: Self::PERIOD_DURATION,
+        }
+    }
+
+    fn roll(seed: u64, period: u64, rates: &WeatherRates) -> Weather {
+        let mut rng = StdRng::seed_from_u64(seed ^ period.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        rates
+            .get_random(&mut rng)
+            .first()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn advance(&mut self, seconds: f32, rates: &WeatherRates) {
+        self.remaining -= seconds;
+        while self.remaining <= 0. {
+            self.remaining += Self::PERIOD_DURATION;
+            self.period += 1;
+            self.current = Self::roll(self.seed, self.period, rates);
+        }
+    }
+
+    /// Forces the weather until the end of a whole period
+    pub fn set(&mut self, weather: Weather) {
+        self.current = weather;
+        self.remaining = Self::PERIOD_DURATION;
+    }
+}
+
+impl Display for WorldWeather {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} for {:.0}s (seed {}, period {})",
+            self.current, self.remaining, self.seed, self.period
+        )
+    }
+}
+
+struct WeatherParticles {
+    /// Spawned every [`WeatherParticleTimer::SECONDS`]
+    count: usize,
+    size: Vec2,
+    color: Color,
+    velocity: Vec2,
+    /// Whether particles come from the left of the screen instead of the top
+    from_side: bool,
+}
+
+#[derive(Resource)]
+struct WeatherParticleTimer(Timer);
+
+impl WeatherParticleTimer {
+    const SECONDS: f32 = 0.03;
+}
+
+#[derive(Component)]
+pub struct WeatherParticle {
+    velocity: Vec2,
+    lifetime: Timer,
+}
+
+impl WeatherParticle {
+    const Z: f32 = 50.;
+}
+
+/// Tinted sprite following the camera, its alpha reduces the visibility
+#[derive(Component)]
+pub struct WeatherOverlay;
+
+fn tick_weather(mut query: Query<(&World, &mut WorldWeather)>, time: Res<Time>) {
+    for (world, mut weather) in query.iter_mut() {
+        weather.advance(time.delta_seconds(), &world.weather_rates());
+    }
+}
+
+fn update_weather_overlay(
+    mut commands: Commands,
+    weather_query: Query<&WorldWeather>,
+    mut overlay_query: Query<&mut Sprite, With<WeatherOverlay>>,
+    camera_query: Query<Entity, With<Camera2d>>,
+) {
+    let weather = weather_query
+        .get_single()
+        .map(|weather| weather.current)
+        .unwrap_or_default();
+
+    if let Ok(mut sprite) = overlay_query.get_single_mut() {
+        sprite.color = weather.overlay_color();
+        return;
+    }
+
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+
+    commands.entity(camera).with_children(|builder| {
+        builder.spawn((
+            WeatherOverlay,
+            SpriteBundle {
+                sprite: Sprite {
+                    color: weather.overlay_color(),
+                    custom_size: Some(Vec2::splat(100_000.)),
+                    ..Default::default()
+                },
+                // Right in front of the camera so it covers the whole world
+                transform: Transform::from_xyz(0., 0., -1.),
+                ..Default::default()
+            },
+        ));
+    });
+}
+
+fn spawn_weather_particles(
+    mut commands: Commands,
+    mut timer: ResMut<WeatherParticleTimer>,
+    weather_query: Query<&WorldWeather>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<Camera2d>>,
+    time: Res<Time>,
+) {
+    timer.0.tick(time.delta());
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    let Ok(weather) = weather_query.get_single() else {
+        return;
+    };
+
+    let Some(particles) = weather.current.particles() else {
+        return;
+    };
+
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let area = Rect {
+        min: projection.area.min + camera_transform.translation.xy(),
+        max: projection.area.max + camera_transform.translation.xy(),
+    };
+    let travel = if particles.from_side {
+        area.width() / particles.velocity.x.abs()
+    } else {
+        area.height() / particles.velocity.y.abs()
+    };
+
+    let mut rng = thread_rng();
+    for _ in 0..particles.count {
+        let pos = if particles.from_side {
+            Vec2::new(area.min.x, rng.gen_range(area.min.y..=area.max.y))
+        } else {
+            // Wider than the screen as the particles drift sideways while falling
+            Vec2::new(
+                rng.gen_range(area.min.x..=area.max.x + area.width() * 0.2),
+                area.max.y,
+            )
+        };
+
+        let speed: f32 = rng.gen_range(0.8..=1.2);
+
+        commands.spawn((
+            WeatherParticle {
+                velocity: particles.velocity * speed,
+                lifetime: Timer::from_seconds(travel * 1.2, TimerMode::Once),
+            },
+            SpriteBundle {
+                sprite: Sprite {
+                    color: particles.color,
+                    custom_size: Some(particles.size),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(pos.extend(WeatherParticle::Z)),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+fn update_weather_particles(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut WeatherParticle, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut particle, mut transform) in query.iter_mut() {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += (particle.velocity * time.delta_seconds()).extend(0.);
+    }
+}
+
+fn despawn_weather(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<WeatherParticle>, With<WeatherOverlay>)>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
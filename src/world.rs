@@ -1,25 +1,35 @@
 use crate::background::ParallaxBackground;
+use crate::day_night::TimeOfDay;
+use crate::dungeon::{DungeonRooms, DungeonSeed, DungeonTemplates};
 use crate::gui::main_menu::MainMenuState;
 use crate::gui::misc::{ease_in_quad, ease_out_quad};
 use crate::gui::styles::text_style;
 use crate::lang::Lang;
+use crate::mob::definition::MobDefinitions;
 use crate::mob::list::pig::Pig;
 use crate::mob::list::rabbit::Rabbit;
 use crate::mob::list::MobObject;
-use crate::mob::MobTrait;
-use crate::random::{RandomWeightedRate, RandomWeightedTable};
+use crate::mob::spawn::MobPopulation;
+use crate::portal::PendingSpawnPoint;
+use crate::random::{RandomWeightedRate, RandomWeightedTable, WorldRng};
+use crate::save::{CurrentSave, WorldSave, WorldSnapshot};
 use crate::state::AppState;
-use crate::tiled::{Loaded, TiledMapBundle};
-use bevy::{asset::AssetPath, prelude::*};
+use crate::tiled::{Loaded, TiledLayersStorage, TiledMap, TiledMapBundle};
+use crate::weather::{Weather, WeatherRates, WorldWeather};
+use bevy::{
+    asset::{AssetPath, LoadState},
+    prelude::*,
+};
 use enum_dispatch::enum_dispatch;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 pub const BLOCK_SIZE: f32 = 16.;
 
-#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum World {
     Biome(Biome),
     Dungeon(Dungeon),
@@ -58,39 +68,83 @@ impl World {
         }
     }
 
+    /// Level the player needs to enter the world through a portal
+    pub fn required_level(&self) -> u32 {
+        match self {
+            Self::Biome(biome) => biome.required_level(),
+            Self::Dungeon(dungeon) => dungeon.required_level(),
+        }
+    }
+
+    pub fn spawn_point(&self) -> &'static str {
+        match self {
+            Self::Biome(biome) => biome.spawn_point(),
+            Self::Dungeon(dungeon) => dungeon.spawn_point(),
+        }
+    }
+
+    /// # Returns
+    /// Every world of the game
+    pub fn all() -> Vec<Self> {
+        vec![
+            Biome::from(PlainsBiome).into(),
+            Biome::from(ForestBiome).into(),
+            Biome::from(DesertBiome).into(),
+            Dungeon::from(PyramidDungeon).into(),
+        ]
+    }
+
+    /// # Returns
+    /// The odds of each weather, dungeons are always clear
+    pub fn weather_rates(&self) -> WeatherRates {
+        match self {
+            Self::Biome(biome) => biome.weather_rates(),
+            Self::Dungeon(_) => WeatherRates::new_empty(),
+        }
+    }
+
+    /// # Returns
+    /// How many mobs can be alive at once, dungeons are only limited by their spawn zones
+    pub fn max_mobs(&self) -> usize {
+        match self {
+            Self::Biome(biome) => biome.max_mobs(),
+            Self::Dungeon(_) => usize::MAX,
+        }
+    }
+
     pub fn tile_set_path(&self) -> TileMapAsset {
         TileMapAsset(Path::new(self.get_type()).join(self.name()))
     }
 
+    /// Path of a room template of a generated dungeon
+    pub fn room_path(&self, room: &str) -> TileMapAsset {
+        TileMapAsset(Path::new(self.get_type()).join(self.name()).join(room))
+    }
+
+    /// # Returns
+    /// The rooms the world is generated from, [`None`] for hand made maps
+    pub fn dungeon_templates(&self) -> Option<DungeonTemplates> {
+        match self {
+            Self::Biome(_) => None,
+            Self::Dungeon(dungeon) => dungeon.templates(),
+        }
+    }
+
     pub fn spawn(
         self,
         commands: &mut Commands,
         asset_server: &Res<AssetServer>,
         lang: &Res<Lang>,
         camera: Entity,
+        world_save: Option<&WorldSave>,
+        definitions: &MobDefinitions,
+        rng: &mut WorldRng,
     ) -> Entity {
-        let tiled_map = asset_server.load(self.tile_set_path());
-
-        commands.spawn((
-            WorldEnterText {
-                timer: Timer::from_seconds(5.0, TimerMode::Once),
-            },
-            TextBundle {
-                text: Text::from_section(
-                    lang.get(&format!("world.{}.{}", self.get_type(), self.name())),
-                    TextStyle {
-                        font_size: 64.,
-                        ..text_style(asset_server)
-                    },
-                )
-                .with_alignment(TextAlignment::Center),
-                style: Style {
-                    margin: UiRect::axes(Val::Auto, Val::Percent(-5.0)),
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-        ));
+        WorldEnterText::spawn(
+            commands,
+            asset_server,
+            lang.get(&format!("world.{}.{}", self.get_type(), self.name())),
+        );
         let background = match &self {
             Self::Biome(b) => b.background(),
             Self::Dungeon(d) => d.background(),
@@ -113,43 +167,51 @@ impl World {
             });
         }
 
-        let mobs = if let World::Biome(biome) = &self {
-            let spawn_rates = biome.mob_spawn_rate();
-            spawn_rates
-                .get_random()
-                .into_iter()
-                .flat_map(|mob| {
-                    let n = thread_rng().gen_range(mob.group);
-                    (0..n).map(move |_| {
-                        MobTrait::bundle(mob.mob.clone(), asset_server, Vec2::default())
-                    })
+        // New mobs come from the spawn zones of the map once it is loaded
+        if let Some(world_save) = world_save {
+            for mob in world_save.mobs.iter() {
+                commands.spawn(mob.into_bundle(asset_server, definitions));
+            }
+        }
+
+        let weather = world_save
+            .and_then(|world_save| world_save.weather.clone())
+            .unwrap_or_else(|| WorldWeather::new(rng.gen(), &self.weather_rates()));
+
+        let map = match self.dungeon_templates() {
+            // Same layout as last time if the save was made inside the dungeon
+            Some(templates) => commands
+                .spawn((
+                    templates.load(&self, asset_server),
+                    world_save
+                        .and_then(|world_save| world_save.dungeon_seed)
+                        .map(DungeonSeed)
+                        .unwrap_or_else(|| DungeonSeed(rng.gen())),
+                    TiledLayersStorage::default(),
+                    TransformBundle::default(),
+                ))
+                .id(),
+            None => commands
+                .spawn(TiledMapBundle {
+                    tiled_map: asset_server.load(self.tile_set_path()),
+                    ..Default::default()
                 })
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
+                .id(),
         };
 
-        let entity = commands
-            .spawn(self)
-            .insert(TiledMapBundle {
-                tiled_map,
-                ..Default::default()
-            })
-            .insert(InheritedVisibility::VISIBLE)
-            .id();
-
-        mobs.into_iter().for_each(|bundle| {
-            let mob = commands.spawn(bundle).id();
-            commands.entity(entity).add_child(mob);
-        });
-
-        entity
+        commands.entity(map).insert((
+            self,
+            weather,
+            MobPopulation::default(),
+            InheritedVisibility::VISIBLE,
+        ));
+        map
     }
 }
 
 #[enum_dispatch(WorldTrait)]
 #[enum_dispatch(BiomeTrait)]
-#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash, Debug)]
 
 pub enum Biome {
     Plains(PlainsBiome),
@@ -157,6 +219,16 @@ pub enum Biome {
     Desert(DesertBiome),
 }
 
+impl Biome {
+    /// # Returns
+    /// The spawn rates of the biome with the [`MobDefinitions`] living there
+    pub fn spawn_rates(&self, time: TimeOfDay, definitions: &MobDefinitions) -> MobSpawnRates {
+        let mut rates = self.mob_spawn_rate(time);
+        rates.extend(definitions.biome_spawn_rates(self.name(), time));
+        rates
+    }
+}
+
 impl Default for Biome {
     fn default() -> Self {
         PlainsBiome.into()
@@ -164,7 +236,8 @@ impl Default for Biome {
 }
 
 #[enum_dispatch(WorldTrait)]
-#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash)]
+#[enum_dispatch(DungeonTrait)]
+#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Dungeon {
     Pyramid(PyramidDungeon),
 }
@@ -172,11 +245,14 @@ pub enum Dungeon {
 pub struct WorldPlugin;
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, world_text_update.run_if(in_state(AppState::InGame)))
-            .add_systems(
-                OnEnter(AppState::MainMenu(MainMenuState::Default)),
-                despawn_world_text,
-            );
+        app.add_systems(
+            Update,
+            (world_text_update, world_load_failed).run_if(in_state(AppState::InGame)),
+        )
+        .add_systems(
+            OnEnter(AppState::MainMenu(MainMenuState::Default)),
+            despawn_world_text,
+        );
     }
 }
 
@@ -206,85 +282,318 @@ fn world_text_update(
     }
 }
 
+/// Falls back to the default world when the map of the current one can't be loaded
+fn world_load_failed(
+    mut commands: Commands,
+    query: Query<(&World, Option<&Handle<TiledMap>>, Option<&DungeonRooms>), Without<Loaded>>,
+    snapshot: WorldSnapshot,
+    mut current_save: ResMut<CurrentSave>,
+    mut pending_spawn_point: ResMut<PendingSpawnPoint>,
+    mut state_change: ResMut<NextState<AppState>>,
+    camera_query: Query<Entity, With<Camera>>,
+    asset_server: Res<AssetServer>,
+    lang: Res<Lang>,
+    definitions: Res<MobDefinitions>,
+    mut rng: ResMut<WorldRng>,
+) {
+    for (world, handle, rooms) in query.iter() {
+        let failed = handle
+            .into_iter()
+            .chain(rooms.into_iter().flat_map(DungeonRooms::handles))
+            .any(|handle| asset_server.get_load_state(handle) == Some(LoadState::Failed));
+
+        if !failed {
+            continue;
+        }
+
+        error!(
+            "Map of world {}.{} could not be loaded",
+            world.get_type(),
+            world.name()
+        );
+        snapshot.despawn(&mut commands);
+
+        let Some(save_data) = &mut current_save.0 else {
+            continue;
+        };
+
+        let fallback = World::default();
+        if *world == fallback {
+            current_save.0 = None;
+            state_change.set(AppState::MainMenu(MainMenuState::Default));
+            continue;
+        }
+
+        save_data.data.current_world = fallback.clone();
+        let world_save = save_data.data.worlds.get(&fallback);
+        fallback.spawn(
+            &mut commands,
+            &asset_server,
+            &lang,
+            camera_query.single(),
+            world_save,
+            &definitions,
+            &mut rng,
+        );
+        pending_spawn_point.0 = Some(String::new());
+    }
+}
+
 fn despawn_world_text(mut commands: Commands, query: Query<Entity, With<WorldEnterText>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
     }
 }
 
+/// Title sliding from the top of the screen, shown when entering a world or on other big events
 #[derive(Component)]
 pub struct WorldEnterText {
     timer: Timer,
 }
 
+impl WorldEnterText {
+    pub fn spawn(commands: &mut Commands, asset_server: &AssetServer, text: &str) {
+        commands.spawn((
+            WorldEnterText {
+                timer: Timer::from_seconds(5.0, TimerMode::Once),
+            },
+            TextBundle {
+                text: Text::from_section(
+                    text,
+                    TextStyle {
+                        font_size: 64.,
+                        ..text_style(asset_server)
+                    },
+                )
+                .with_alignment(TextAlignment::Center),
+                style: Style {
+                    margin: UiRect::axes(Val::Auto, Val::Percent(-5.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ));
+    }
+}
+
 #[enum_dispatch]
 pub trait WorldTrait: Sync + Send {
     fn name(&self) -> &'static str;
 
+    /// [`SpawnPoint`](crate::portal::SpawnPoint) where the player respawns without a checkpoint
+    fn spawn_point(&self) -> &'static str;
+
     fn background(&self) -> Option<(u32, Vec2)> {
         None
     }
+
+    fn required_level(&self) -> u32 {
+        1
+    }
 }
 
 #[enum_dispatch]
 pub trait BiomeTrait: Sync + Send {
-    fn mob_spawn_rate(&self) -> MobSpawnRates {
+    fn mob_spawn_rate(&self, _time: TimeOfDay) -> MobSpawnRates {
         MobSpawnRates::new_empty()
     }
+
+    fn weather_rates(&self) -> WeatherRates {
+        WeatherRates::new_empty()
+    }
+
+    fn max_mobs(&self) -> usize {
+        8
+    }
 }
 
-#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct DesertBiome;
 impl WorldTrait for DesertBiome {
     fn name(&self) -> &'static str {
         "desert"
     }
+
+    fn spawn_point(&self) -> &'static str {
+        "from_forest"
+    }
+
+    fn background(&self) -> Option<(u32, Vec2)> {
+        Some((4, Vec2::new(576., 324.)))
+    }
+
+    fn required_level(&self) -> u32 {
+        3
+    }
+}
+
+impl BiomeTrait for DesertBiome {
+    fn mob_spawn_rate(&self, _time: TimeOfDay) -> MobSpawnRates {
+        MobSpawnRates::new(
+            3,
+            vec![RandomWeightedRate {
+                data: MobSpawnRate {
+                    mob: Rabbit.into(),
+                    group: 1..=2,
+                },
+                weight: 1,
+            }],
+        )
+    }
+
+    fn weather_rates(&self) -> WeatherRates {
+        WeatherRates::new(
+            1,
+            vec![
+                RandomWeightedRate {
+                    data: Weather::Clear,
+                    weight: 5,
+                },
+                RandomWeightedRate {
+                    data: Weather::Sandstorm,
+                    weight: 2,
+                },
+            ],
+        )
+    }
+
+    fn max_mobs(&self) -> usize {
+        6
+    }
 }
-impl BiomeTrait for DesertBiome {}
 
-#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct ForestBiome;
 impl WorldTrait for ForestBiome {
     fn name(&self) -> &'static str {
         "forest"
     }
+
+    fn spawn_point(&self) -> &'static str {
+        "from_plains"
+    }
+
+    fn background(&self) -> Option<(u32, Vec2)> {
+        Some((4, Vec2::new(576., 324.)))
+    }
 }
-impl BiomeTrait for ForestBiome {}
 
-#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash)]
+impl BiomeTrait for ForestBiome {
+    fn mob_spawn_rate(&self, time: TimeOfDay) -> MobSpawnRates {
+        let mut rates = vec![RandomWeightedRate {
+            data: MobSpawnRate {
+                mob: Pig.into(),
+                group: 1..=2,
+            },
+            weight: 1,
+        }];
+
+        // Rabbits hide in their burrows at night
+        if time != TimeOfDay::Night {
+            rates.push(RandomWeightedRate {
+                data: MobSpawnRate {
+                    mob: Rabbit.into(),
+                    group: 2..=4,
+                },
+                weight: 3,
+            });
+        }
+
+        MobSpawnRates::new(6, rates)
+    }
+
+    fn weather_rates(&self) -> WeatherRates {
+        WeatherRates::new(
+            1,
+            vec![
+                RandomWeightedRate {
+                    data: Weather::Clear,
+                    weight: 3,
+                },
+                RandomWeightedRate {
+                    data: Weather::Rain,
+                    weight: 2,
+                },
+                RandomWeightedRate {
+                    data: Weather::Fog,
+                    weight: 2,
+                },
+            ],
+        )
+    }
+
+    fn max_mobs(&self) -> usize {
+        12
+    }
+}
+
+#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct PlainsBiome;
 impl WorldTrait for PlainsBiome {
     fn name(&self) -> &'static str {
         "plains"
     }
 
+    fn spawn_point(&self) -> &'static str {
+        "start"
+    }
+
     fn background(&self) -> Option<(u32, Vec2)> {
         Some((4, Vec2::new(576., 324.)))
     }
 }
 
 impl BiomeTrait for PlainsBiome {
-    fn mob_spawn_rate(&self) -> MobSpawnRates {
-        MobSpawnRates::new(
-            5,
+    fn mob_spawn_rate(&self, time: TimeOfDay) -> MobSpawnRates {
+        let mut rates = vec![RandomWeightedRate {
+            data: MobSpawnRate {
+                mob: Pig.into(),
+                group: 1..=3,
+            },
+            weight: 1,
+        }];
+
+        if time != TimeOfDay::Night {
+            rates.push(RandomWeightedRate {
+                data: MobSpawnRate {
+                    mob: Rabbit.into(),
+                    group: 1..=3,
+                },
+                weight: 1,
+            });
+        }
+
+        MobSpawnRates::new(5, rates)
+    }
+
+    fn weather_rates(&self) -> WeatherRates {
+        WeatherRates::new(
+            1,
             vec![
                 RandomWeightedRate {
-                    data: MobSpawnRate {
-                        mob: Rabbit.into(),
-                        group: 1..=3,
-                    },
-                    weight: 1,
+                    data: Weather::Clear,
+                    weight: 4,
                 },
                 RandomWeightedRate {
-                    data: MobSpawnRate {
-                        mob: Pig.into(),
-                        group: 1..=3,
-                    },
-                    weight: 1,
+                    data: Weather::Rain,
+                    weight: 2,
                 },
             ],
         )
     }
+
+    fn max_mobs(&self) -> usize {
+        10
+    }
+}
+
+#[enum_dispatch]
+pub trait DungeonTrait: Sync + Send {
+    /// # Returns
+    /// The rooms the dungeon is generated from, [`None`] for a hand made map
+    fn templates(&self) -> Option<DungeonTemplates> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -295,12 +604,47 @@ pub struct MobSpawnRate {
 
 pub type MobSpawnRates = RandomWeightedTable<MobSpawnRate>;
 
-#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Component, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct PyramidDungeon;
 impl WorldTrait for PyramidDungeon {
     fn name(&self) -> &'static str {
         "pyramid"
     }
+
+    fn spawn_point(&self) -> &'static str {
+        "entrance"
+    }
+
+    fn background(&self) -> Option<(u32, Vec2)> {
+        Some((1, Vec2::new(576., 324.)))
+    }
+
+    fn required_level(&self) -> u32 {
+        5
+    }
+}
+
+impl DungeonTrait for PyramidDungeon {
+    fn templates(&self) -> Option<DungeonTemplates> {
+        Some(DungeonTemplates {
+            entrance: "entrance",
+            rooms: &["corridor", "hall", "pillars", "treasure"],
+            boss: Some("boss"),
+            room_count: 6,
+        })
+    }
+}
+
+impl FromStr for World {
+    type Err = ();
+
+    /// Parses `type.name`, e.g. `biome.plains` or `dungeon.pyramid`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|world| format!("{}.{}", world.get_type(), world.name()) == s)
+            .ok_or(())
+    }
 }
 
 pub struct TileMapAsset(PathBuf);
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use terradventure::{
+    effects::{Effect, EffectsController},
+    items::{list::mana_potion::ManaPotion, stack::ItemStack},
+    player::{
+        class::{wizard::Wizard, PlayerClasses},
+        level::{PlayerLevel, StatPoints},
+        Player,
+    },
+    save::{PlayerSave, Save},
+    stats::{Resistances, Stats},
+    world::{ForestBiome, World},
+};
+
+#[test]
+fn player_round_trip() {
+    let level = PlayerLevel {
+        level: 7,
+        xp: 42,
+        stat_points: 2,
+        spent: StatPoints {
+            strength: 4,
+            mana: 1,
+            ..Default::default()
+        },
+    };
+
+    let mut player = Player::default();
+    player.class = PlayerClasses::Wizard(Wizard);
+    player.mana.set_max(level.mana_capacity());
+    player.mana.try_remove(12.5);
+    player.inventory.ressources[0].item = Some(ItemStack {
+        item: ManaPotion.into(),
+        count: 3,
+    });
+
+    let mut effects = EffectsController::default();
+    effects.add_new(Effect::Burning, 4., 1);
+    effects.add_new(Effect::Swiftness, 30., 2);
+
+    let save = Save {
+        player: PlayerSave {
+            player,
+            stats: Stats {
+                strength: 3.,
+                health: 12.5,
+                max_health: 30.,
+                def: 2.,
+                resistances: Resistances {
+                    fire: 0.25,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            pos: Vec2::new(-120., 48.),
+            effects,
+            facing_left: true,
+            level,
+        },
+        current_world: World::Biome(ForestBiome.into()),
+        ..Default::default()
+    };
+
+    let mut decoded = Save::decode(&save.encode().unwrap()).unwrap();
+    // The max mana isn't saved, the player setup takes it back from the level
+    let capacity = decoded.player.level.mana_capacity();
+    decoded.player.player.mana.set_max(capacity);
+    assert_eq!(decoded.player, save.player);
+    assert_eq!(decoded.current_world, save.current_world);
+}